@@ -0,0 +1,10 @@
+#![no_main]
+
+use aoc25::day12::Day12;
+use aoc25::solution::Solution;
+use libfuzzer_sys::fuzz_target;
+
+// malformed input must come back as an error, never as a panic or a hang
+fuzz_target!(|input: &str| {
+    let _ = Day12::parse(input);
+});
@@ -0,0 +1,126 @@
+// Answers the puzzle site accepted, kept in a toml file next to the inputs:
+//
+//   [day01]
+//   part1 = 964
+//   part2 = 5872
+//
+// Answers are either integers or strings. An answer too large for a toml integer is written as a
+// string of digits. Missing days or parts are reported as unknown.
+
+use std::fmt;
+
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Part};
+
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+pub struct KnownAnswers {
+    table: toml::Table,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: Answer },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => f.pad("PASS"),
+            Verdict::Fail { .. } => f.pad("FAIL"),
+            Verdict::Unknown => f.pad("UNKNOWN"),
+        }
+    }
+}
+
+impl KnownAnswers {
+    pub fn load(path: &str) -> Result<KnownAnswers> {
+        let content = std::fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+        content.parse()
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<Answer> {
+        let value = self
+            .table
+            .get(&format!("day{:02}", day))?
+            .get(format!("part{}", part))?;
+
+        match value {
+            toml::Value::Integer(n) => Some(Answer::Int(*n)),
+            toml::Value::String(s) => match s.parse::<u128>() {
+                Ok(n) if i64::try_from(n).is_err() => Some(Answer::from(n)),
+                _ => Some(Answer::Text(s.clone())),
+            },
+            _ => None,
+        }
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &Answer) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == *answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail { expected },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl std::str::FromStr for KnownAnswers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let table: toml::Table = s.parse().map_err(|e: toml::de::Error| {
+            let offset = e.span().map(|span| span.start).unwrap_or(s.len());
+            Error::parse_at(s, &s[offset..], e.message())
+        })?;
+        Ok(KnownAnswers { table })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_ANSWERS: &str = r#"
+[day01]
+part1 = 964
+part2 = "abc"
+
+[day02]
+part2 = "18446744073709551615"
+"#;
+
+    #[test]
+    fn test_check() {
+        let answers: KnownAnswers = TEST_ANSWERS.parse().unwrap();
+
+        assert_eq!(
+            answers.check(1, Part::One, &Answer::Int(964)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(1, Part::One, &Answer::Int(963)),
+            Verdict::Fail {
+                expected: Answer::Int(964)
+            }
+        );
+        assert_eq!(
+            answers.check(1, Part::Two, &Answer::from("abc")),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(2, Part::One, &Answer::Int(1)),
+            Verdict::Unknown
+        );
+        assert_eq!(
+            answers.check(2, Part::Two, &Answer::from(u64::MAX)),
+            Verdict::Pass
+        );
+    }
+
+    #[test]
+    fn test_repo_answers_file_parses() {
+        KnownAnswers::load(DEFAULT_PATH).unwrap();
+    }
+}
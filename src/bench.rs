@@ -0,0 +1,201 @@
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::input::Input;
+use crate::runner::{self, Format};
+use crate::solution::{Answer, Day, Part};
+
+/// Summary statistics over repeated timings.
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", serialize_with = "as_nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "as_nanos")]
+    pub median: Duration,
+    #[serde(rename = "mean_ns", serialize_with = "as_nanos")]
+    pub mean: Duration,
+    #[serde(rename = "stddev_ns", serialize_with = "as_nanos")]
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "at least one sample is required");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+
+        let mean_secs = sorted.iter().map(|d| d.as_secs_f64()).sum::<f64>() / n as f64;
+        // sample standard deviation, a single run has no spread
+        let variance = if n > 1 {
+            sorted
+                .iter()
+                .map(|d| (d.as_secs_f64() - mean_secs).powi(2))
+                .sum::<f64>()
+                / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean_secs),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+/// Runs parse and solve `warmup` times untimed, then `iterations` times timing each step. The
+/// strategy is used if the part has one by that name, see `runner::run_with`.
+pub fn bench(
+    day: &Day,
+    input: &str,
+    part: Part,
+    strategy: Option<&str>,
+    warmup: usize,
+    iterations: usize,
+) -> Result<BenchReport> {
+    assert!(iterations > 0, "at least one iteration is required");
+    let _span = tracing::info_span!("bench", day = day.day, part = %part).entered();
+
+    for _ in 0..warmup {
+        let parsed = day.parse(input)?;
+        runner::solve(day, parsed.as_ref(), part, strategy)?;
+    }
+
+    let mut parse_times = Vec::with_capacity(iterations);
+    let mut solve_times = Vec::with_capacity(iterations);
+    let mut answer = None;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = day.parse(input)?;
+        parse_times.push(start.elapsed());
+
+        let start = Instant::now();
+        answer = Some(runner::solve(day, parsed.as_ref(), part, strategy)?);
+        solve_times.push(start.elapsed());
+    }
+
+    Ok(BenchReport {
+        day: day.day,
+        part,
+        answer: answer.expect("at least one iteration ran"),
+        warmup,
+        iterations,
+        parse: Stats::from_samples(&parse_times),
+        solve: Stats::from_samples(&solve_times),
+    })
+}
+
+/// `bench` for each part of a loaded input, a parse error says which file it was and how it was
+/// normalized. A named strategy may be one of the exhaustive searches a day keeps around, so the
+/// benchmark is given up on if it runs longer than `timeout` in total.
+pub fn bench_parts(
+    day: &'static Day,
+    input: &Input,
+    parts: &[Part],
+    strategy: Option<&str>,
+    timeout: Duration,
+    warmup: usize,
+    iterations: usize,
+) -> Result<Vec<BenchReport>> {
+    let Some(name) = strategy else {
+        return bench_input(day, input, parts, None, warmup, iterations);
+    };
+    let (input, parts, owned) = (input.clone(), parts.to_vec(), name.to_string());
+    runner::with_strategy_timeout(name, timeout, move || {
+        bench_input(day, &input, &parts, Some(&owned), warmup, iterations)
+    })
+}
+
+fn bench_input(
+    day: &Day,
+    input: &Input,
+    parts: &[Part],
+    strategy: Option<&str>,
+    warmup: usize,
+    iterations: usize,
+) -> Result<Vec<BenchReport>> {
+    parts
+        .iter()
+        .map(|&part| bench(day, &input.text, part, strategy, warmup, iterations))
+        .collect::<Result<Vec<_>>>()
+        .map_err(|e| input.context(e))
+}
+
+/// Prints the reports as a paragraph per part or as json.
+pub fn print(reports: &[BenchReport], format: Format) {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(reports).unwrap()),
+        Format::Text => {
+            for report in reports {
+                println!(
+                    "day {} part {}: {} ({} iterations after {} warm-up)",
+                    report.day, report.part, report.answer, report.iterations, report.warmup
+                );
+                for (step, stats) in [("parse", &report.parse), ("solve", &report.solve)] {
+                    println!(
+                        "  {}  min {:>10}  median {:>10}  mean {:>10}  stddev {:>10}",
+                        step,
+                        format!("{:.2?}", stats.min),
+                        format!("{:.2?}", stats.median),
+                        format!("{:.2?}", stats.mean),
+                        format!("{:.2?}", stats.stddev)
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn as_nanos<S: serde::Serializer>(d: &Duration, s: S) -> std::result::Result<S::Ok, S::Error> {
+    s.serialize_u64(d.as_nanos() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = [4, 1, 3, 2]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean.as_micros(), 2500);
+        // sqrt(5/3) ms
+        assert_eq!(stats.stddev.as_micros(), 1290);
+    }
+
+    #[test]
+    fn test_stats_single_sample() {
+        let stats = Stats::from_samples(&[Duration::from_millis(7)]);
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+}
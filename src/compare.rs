@@ -0,0 +1,212 @@
+// Runs every strategy a day registers for a part against the same input, to check they agree and
+// to see how their timings stack up. Some strategies are the exhaustive searches the fast ones
+// replaced and won't finish on a real input, so each runs on its own thread and is given up on
+// after a timeout. An abandoned strategy keeps its thread busy until the process exits, which
+// can slow down the strategies timed after it.
+
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::runner;
+use crate::solution::{Answer, Day, Part};
+
+#[derive(Debug)]
+pub enum Outcome {
+    Solved { answer: Answer, time: Duration },
+    Failed(Error),
+    TimedOut,
+}
+
+#[derive(Debug)]
+pub struct StrategyRun {
+    pub strategy: &'static str,
+    pub outcome: Outcome,
+}
+
+/// Every strategy's outcome for one part, the default strategy first.
+#[derive(Debug)]
+pub struct Comparison {
+    pub part: Part,
+    pub runs: Vec<StrategyRun>,
+}
+
+impl Comparison {
+    /// Whether the strategies that finished all came up with the same answer. A strategy that
+    /// failed disagrees with one that answered.
+    pub fn agree(&self) -> bool {
+        let mut finished = self.runs.iter().filter_map(|run| match &run.outcome {
+            Outcome::Solved { answer, .. } => Some(Some(answer)),
+            Outcome::Failed(_) => Some(None),
+            Outcome::TimedOut => None,
+        });
+        match finished.next() {
+            Some(first) => finished.all(|answer| answer == first),
+            None => true,
+        }
+    }
+}
+
+/// Solves the parts with each of their strategies in turn. The input is parsed up front so that
+/// a parse error is reported once rather than against every strategy.
+pub fn compare(
+    day: &'static Day,
+    input: &Input,
+    parts: &[Part],
+    timeout: Duration,
+) -> Result<Vec<Comparison>> {
+    day.parse(&input.text).map_err(|e| input.context(e))?;
+
+    Ok(parts
+        .iter()
+        .map(|&part| Comparison {
+            part,
+            runs: day
+                .strategies(part)
+                .into_iter()
+                .map(|strategy| StrategyRun {
+                    strategy,
+                    outcome: run_strategy(day, &input.text, part, strategy, timeout),
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+/// Prints each strategy's answer and timing relative to the default strategy, returns false when
+/// strategies that finished disagree.
+pub fn print(day: &Day, comparisons: &[Comparison]) -> bool {
+    let mut agreed = true;
+    for comparison in comparisons {
+        println!("day {} part {}", day.day, comparison.part);
+        let width = comparison
+            .runs
+            .iter()
+            .map(|run| run.strategy.len())
+            .max()
+            .unwrap_or(0);
+
+        let baseline = match &comparison.runs[0].outcome {
+            Outcome::Solved { time, .. } => Some(*time),
+            _ => None,
+        };
+        for run in &comparison.runs {
+            let outcome = match &run.outcome {
+                Outcome::Solved { answer, time } => format!(
+                    "{:<20}  {:>10}{}",
+                    answer.to_string(),
+                    format!("{:.2?}", time),
+                    baseline
+                        .map(|b| format!("  {:>8.2}x", time.as_secs_f64() / b.as_secs_f64()))
+                        .unwrap_or_default()
+                ),
+                Outcome::Failed(e) => format!("error: {}", e),
+                Outcome::TimedOut => "gave up, it took longer than the timeout".to_string(),
+            };
+            println!("  {:<width$}  {}", run.strategy, outcome, width = width);
+        }
+
+        let finished = comparison
+            .runs
+            .iter()
+            .filter(|run| !matches!(run.outcome, Outcome::TimedOut))
+            .count();
+        if comparison.runs.len() == 1 {
+            println!("  only one strategy");
+        } else if finished < 2 {
+            println!("  too few strategies finished to compare their answers");
+        } else if comparison.agree() {
+            println!("  the strategies agree");
+        } else {
+            agreed = false;
+            println!("  MISMATCH, the strategies disagree");
+        }
+    }
+    agreed
+}
+
+fn run_strategy(
+    day: &'static Day,
+    input: &str,
+    part: Part,
+    strategy: &'static str,
+    timeout: Duration,
+) -> Outcome {
+    let _span = tracing::info_span!("compare", day = day.day, part = %part, strategy).entered();
+    let input = input.to_string();
+    // the parsed input isn't Send, the thread parses its own copy
+    let what = format!("the {} strategy", strategy);
+    let outcome = runner::with_timeout(&what, timeout, move || {
+        let parsed = day.parse(&input)?;
+        let start = Instant::now();
+        let answer = day
+            .solve_with(parsed.as_ref(), part, strategy)
+            .expect("only the day's own strategies are compared")?;
+        Ok((answer, start.elapsed()))
+    });
+
+    match outcome {
+        Ok((answer, time)) => {
+            tracing::info!(%answer, elapsed = ?time, "solved");
+            Outcome::Solved { answer, time }
+        }
+        Err(Error::TimedOut(_)) => {
+            tracing::info!("gave up after {:?}", timeout);
+            Outcome::TimedOut
+        }
+        Err(e) => Outcome::Failed(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_compare_example() {
+        let day = registry::find(10).unwrap();
+        let example = Input::new("example", day.examples()[0].input, day.normalize());
+        let comparisons = compare(day, &example, &Part::BOTH, Duration::from_secs(60)).unwrap();
+
+        let names: Vec<Vec<&str>> = comparisons
+            .iter()
+            .map(|c| c.runs.iter().map(|r| r.strategy).collect())
+            .collect();
+        assert_eq!(names, [["iterative-deepening", "dfs"], ["ilp", "bfs"]]);
+        assert!(comparisons.iter().all(Comparison::agree));
+        assert!(matches!(
+            &comparisons[1].runs[1].outcome,
+            Outcome::Solved {
+                answer: Answer::Int(33),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_agree() {
+        let run = |outcome| StrategyRun {
+            strategy: "s",
+            outcome,
+        };
+        let solved = |n| Outcome::Solved {
+            answer: Answer::Int(n),
+            time: Duration::ZERO,
+        };
+        let comparison = |runs| Comparison {
+            part: Part::One,
+            runs,
+        };
+
+        assert!(comparison(vec![run(solved(1)), run(Outcome::TimedOut), run(solved(1))]).agree());
+        assert!(!comparison(vec![run(solved(1)), run(solved(2))]).agree());
+        assert!(
+            !comparison(vec![
+                run(solved(1)),
+                run(Outcome::Failed(Error::NoSolution("x".to_string())))
+            ])
+            .agree()
+        );
+    }
+}
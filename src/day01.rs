@@ -1,24 +1,67 @@
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Example, Solution};
 use nom::character::complete::anychar;
 use nom::{IResult, character::complete::i32 as nom_i32, sequence::tuple};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
-pub fn solve_problem_1(main_file: &str) -> std::io::Result<()> {
-    let file = File::open(main_file)?;
-    let reader = BufReader::new(file);
-    let mut pos = 50;
-    let mut zero_visits = 0;
+pub struct Day01;
 
-    fn parse_line(input: &str) -> IResult<&str, (char, i32)> {
-        tuple((anychar, nom_i32))(input)
+const EXAMPLE: &str = "\
+L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82";
+
+impl Solution for Day01 {
+    type Input = Vec<(char, i32)>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some(Answer::Int(3)),
+        part2: Some(Answer::Int(6)),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        fn parse_line(input: &str) -> IResult<&str, (char, i32)> {
+            tuple((anychar, nom_i32))(input)
+        }
+
+        let mut rotations = Vec::new();
+        for line in input.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (_, (dir_char, steps)) = parse_line(line).map_err(|e| Error::from_nom(input, e))?;
+            if dir_char != 'L' && dir_char != 'R' {
+                return Err(Error::parse_at(
+                    input,
+                    line,
+                    format!("unexpected dir char '{}'", dir_char),
+                ));
+            }
+            rotations.push((dir_char, steps));
+        }
+        Ok(rotations)
     }
 
-    reader.lines().for_each(|line| {
-        if let Ok((_, (dir_char, steps))) = parse_line(line.unwrap().trim()) {
+    fn part1(rotations: &Self::Input) -> Result<i32> {
+        let mut pos = 50;
+        let mut zero_visits = 0;
+
+        for &(dir_char, steps) in rotations {
             pos = match dir_char {
                 'L' => pos - steps,
                 'R' => pos + steps,
-                _ => panic!("unexpected dir char"),
+                _ => unreachable!("direction is validated by the parser"),
             }
             .rem_euclid(100);
 
@@ -26,25 +69,15 @@ pub fn solve_problem_1(main_file: &str) -> std::io::Result<()> {
                 zero_visits += 1;
             }
         }
-    });
-
-    println!("problem 1: zero was visited {} times", zero_visits);
-
-    Ok(())
-}
-
-pub fn solve_problem_2(main_file: &str) -> std::io::Result<()> {
-    let file = File::open(main_file)?;
-    let reader = BufReader::new(file);
-    let mut pos = 50;
-    let mut zero_visits = 0;
 
-    fn parse_line(input: &str) -> IResult<&str, (char, i32)> {
-        tuple((anychar, nom_i32))(input)
+        Ok(zero_visits)
     }
 
-    reader.lines().for_each(|line| {
-        if let Ok((_, (dir_char, steps))) = parse_line(line.unwrap().trim()) {
+    fn part2(rotations: &Self::Input) -> Result<i32> {
+        let mut pos = 50;
+        let mut zero_visits = 0;
+
+        for &(dir_char, steps) in rotations {
             // accumulate the full rotations
             zero_visits += steps / 100;
 
@@ -68,14 +101,10 @@ pub fn solve_problem_2(main_file: &str) -> std::io::Result<()> {
                     }
                     (pos - remainder).rem_euclid(100)
                 }
-                _ => panic!("unexpected dir char"),
+                _ => unreachable!("direction is validated by the parser"),
             }
         }
-    });
 
-    println!(
-        "problem_2: zero was crossed or visitied {} times",
-        zero_visits
-    );
-    Ok(())
+        Ok(zero_visits)
+    }
 }
@@ -1,3 +1,5 @@
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Example, Solution};
 use nom::{
     IResult,
     character::complete::{char, digit1, multispace0},
@@ -6,34 +8,48 @@ use nom::{
     sequence::{separated_pair, terminated},
 };
 
-pub fn solve_problem_1(main_file: &str) -> std::io::Result<()> {
-    solve_problem_with(main_file, 1, is_twice_repeated)
-}
+pub struct Day02;
+
+const EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+
+impl Solution for Day02 {
+    type Input = Vec<(u64, u64)>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some(Answer::Int(1227775554)),
+        part2: Some(Answer::Int(4174379265)),
+    }];
 
-pub fn solve_problem_2(main_file: &str) -> std::io::Result<()> {
-    solve_problem_with(main_file, 2, |n| smallest_repeating_pattern(n).is_some())
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (_, ranges) = parse_ranges(input).map_err(|e| Error::from_nom(input, e))?;
+        Ok(ranges)
+    }
+
+    fn part1(ranges: &Self::Input) -> Result<u64> {
+        Ok(sum_invalid_numbers(ranges, is_twice_repeated))
+    }
+
+    fn part2(ranges: &Self::Input) -> Result<u64> {
+        Ok(sum_invalid_numbers(ranges, |n| {
+            smallest_repeating_pattern(n).is_some()
+        }))
+    }
 }
 
-pub fn solve_problem_with<F>(main_file: &str, num: i32, f: F) -> std::io::Result<()>
+pub fn sum_invalid_numbers<F>(ranges: &[(u64, u64)], f: F) -> u64
 where
     F: Fn(u64) -> bool,
 {
-    let input = std::fs::read_to_string(main_file)?;
-
-    let (_, ranges) = parse_ranges(&input)
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
-
-    let sum: u64 = ranges
+    ranges
         .iter()
         .flat_map(|(low, high)| collect_invalid_numbers(*low, *high, &f))
-        .sum();
-
-    println!("problem {}: Sum of all invalid numbers: {}", num, sum);
-
-    Ok(())
+        .sum()
 }
 
-fn parse_ranges(input: &str) -> IResult<&str, Vec<(u64, u64)>> {
+pub fn parse_ranges(input: &str) -> IResult<&str, Vec<(u64, u64)>> {
     fn parse_u64(input: &str) -> IResult<&str, u64> {
         map_res(digit1, str::parse)(input)
     }
@@ -78,7 +94,7 @@ fn divisors(n: u32) -> Vec<u32> {
     divs
 }
 
-fn is_twice_repeated(n: u64) -> bool {
+pub fn is_twice_repeated(n: u64) -> bool {
     let digit_count = count_digits(n);
 
     // Must be even number of digits
@@ -90,7 +106,7 @@ fn is_twice_repeated(n: u64) -> bool {
 }
 
 // finds the smallest pattern of repeating digits in n, if any
-fn smallest_repeating_pattern(n: u64) -> Option<u64> {
+pub fn smallest_repeating_pattern(n: u64) -> Option<u64> {
     let digit_count = count_digits(n);
 
     // Check all possible pattern lengths (divisors of digit_count)
@@ -123,7 +139,7 @@ fn is_pattern_repeating(n: u64, pattern_length: u32) -> Option<u64> {
     Some(pattern)
 }
 
-fn collect_invalid_numbers<F>(low: u64, high: u64, f: F) -> Vec<u64>
+pub fn collect_invalid_numbers<F>(low: u64, high: u64, f: F) -> Vec<u64>
 where
     F: Fn(u64) -> bool,
 {
@@ -227,6 +243,6 @@ mod tests {
         assert_eq!(divisors(30), vec![1, 2, 3, 5, 6, 10, 15]);
         assert_eq!(divisors(36), vec![1, 2, 3, 4, 6, 9, 12, 18]);
         assert_eq!(divisors(1), vec![1]);
-        assert_eq!(divisors(0), vec![]);
+        assert_eq!(divisors(0), Vec::<u32>::new());
     }
 }
@@ -1,31 +1,58 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-pub fn solve_problem_1(main_file: &str) -> std::io::Result<()> {
-    solve_problem(main_file, find_joltage_2)
-}
-
-pub(crate) fn solve_problem_2(main_file: &str) -> std::io::Result<()> {
-    solve_problem(main_file, |inp| find_joltage_k(inp, 12))
-}
-
-fn solve_problem<F>(main_file: &str, find_joltage: F) -> std::io::Result<()>
-where
-    F: Fn(&[u8]) -> u64,
-{
-    let file = File::open(main_file)?;
-    let reader = BufReader::new(file);
-    let res: u64 = reader
-        .lines()
-        .map(|line| {
-            let digits = input_from_str(line.unwrap().as_str()).expect("invalid line");
-            find_joltage(&digits)
-        })
-        .sum();
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Example, Solution, Strategy};
+
+pub struct Day03;
+
+const EXAMPLE: &str = "\
+987654321111111
+811111111111119
+234234234234278
+818181911112111";
+
+impl Solution for Day03 {
+    type Input = Vec<Vec<u8>>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some(Answer::Int(357)),
+        part2: Some(Answer::Int(3121910778619)),
+    }];
+
+    // picking the best pair directly against keeping the best 2 digits like part 2 keeps 12
+    const PART1_STRATEGIES: &'static [Strategy<Self::Input, u64>] = &[
+        Strategy {
+            name: "suffix-max",
+            solve: Self::part1,
+        },
+        Strategy {
+            name: "greedy-stack",
+            solve: |banks| Ok(banks.iter().map(|digits| find_joltage_k(digits, 2)).sum()),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| input_from_str(line).map_err(|e| e.on_line(i + 1)))
+            .collect()
+    }
 
-    println!("problem 1: the total joltage is {}", res);
+    fn part1(banks: &Self::Input) -> Result<u64> {
+        Ok(banks.iter().map(|digits| find_joltage_2(digits)).sum())
+    }
 
-    Ok(())
+    fn part2(banks: &Self::Input) -> Result<u64> {
+        if let Some(bank) = banks.iter().find(|bank| bank.len() < 12) {
+            return Err(Error::InvalidInput(format!(
+                "a bank of {} batteries cannot turn on 12",
+                bank.len()
+            )));
+        }
+        Ok(banks.iter().map(|digits| find_joltage_k(digits, 12)).sum())
+    }
 }
 
 fn build_suffix_max_array(digits: &[u8]) -> Vec<u8> {
@@ -37,9 +64,13 @@ fn build_suffix_max_array(digits: &[u8]) -> Vec<u8> {
     max_right
 }
 
-fn input_from_str(input: &str) -> Result<Vec<u8>, String> {
+pub fn input_from_str(input: &str) -> Result<Vec<u8>> {
     if input.len() < 2 {
-        return Err("Input too short, need at least 2 characters".to_string());
+        return Err(Error::parse_at(
+            input,
+            input,
+            "Input too short, need at least 2 characters",
+        ));
     }
 
     input
@@ -50,15 +81,19 @@ fn input_from_str(input: &str) -> Result<Vec<u8>, String> {
                 acc.push(b - b'0');
                 Ok(acc)
             } else {
-                Err(format!(
-                    "Invalid character '{}' at position {}, expect 0-9 digits only",
-                    b as char, i
+                Err(Error::parse_at(
+                    input,
+                    &input[i..],
+                    format!(
+                        "Invalid character '{}' at position {}, expect 0-9 digits only",
+                        b as char, i
+                    ),
                 ))
             }
         })
 }
 
-fn find_joltage_2(digits: &[u8]) -> u64 {
+pub fn find_joltage_2(digits: &[u8]) -> u64 {
     let max_right = build_suffix_max_array(digits);
 
     // Find best pair by checking each left position with the max to its right
@@ -73,7 +108,7 @@ fn find_joltage_2(digits: &[u8]) -> u64 {
     best_pair.0 as u64 * 10 + best_pair.1 as u64
 }
 
-fn find_joltage_k(digits: &[u8], k: usize) -> u64 {
+pub fn find_joltage_k(digits: &[u8], k: usize) -> u64 {
     if k > digits.len() {
         panic!(
             "k ({}) cannot be greater than digits length ({})",
@@ -1,49 +1,58 @@
-use nom::IResult;
-use nom::character::complete::one_of;
-use nom::multi::many1;
+use crate::error::Result;
+use crate::grid::Grid;
+use crate::solution::{Answer, Example, Solution};
 use std::fmt;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-pub fn solve_problem_1(main_file: &str) -> std::io::Result<()> {
-    let mut floor = read_floor_file(main_file)?;
-    floor.process_accessible();
-    println!(
-        "problem 1: accessible tiles on floor: {}",
-        floor.accessible_slots.len()
-    );
-    Ok(())
-}
 
-pub fn solve_problem_2(main_file: &str) -> std::io::Result<()> {
-    let mut floor = read_floor_file(main_file)?;
-    let mut tally = 0;
-    while floor.process_accessible() > 0 {
-        tally += floor.accessible_slots.len();
-        floor.clear_acessible();
+pub struct Day04;
+
+const EXAMPLE: &str = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+
+impl Solution for Day04 {
+    type Input = Floor;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some(Answer::Int(13)),
+        part2: Some(Answer::Int(43)),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_floor(input)
     }
-    print!("problem 2: {} rolls of paper were removed", tally);
-    Ok(())
-}
 
-fn read_floor_file(main_file: &str) -> std::io::Result<Floor> {
-    let file = File::open(main_file)?;
-    let reader = BufReader::new(file);
+    fn part1(floor: &Self::Input) -> Result<usize> {
+        let mut floor = floor.clone();
+        floor.process_accessible();
+        Ok(floor.accessible_slots.len())
+    }
 
-    let floor_items = reader
-        .lines()
-        .map(|l| parse_floor_line(l.unwrap().as_str()).unwrap().1)
-        .collect();
+    fn part2(floor: &Self::Input) -> Result<usize> {
+        Ok(floor.removal_waves().iter().map(|wave| wave.len()).sum())
+    }
+}
 
+pub fn parse_floor(input: &str) -> Result<Floor> {
     let floor = Floor {
-        tiles: floor_items,
+        tiles: Grid::parse(input, parse_tile)?,
         accessible_slots: Vec::new(),
     };
     Ok(floor)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Tile {
+pub enum Tile {
     EmptySpace,
     PaperRoll {
         accessible: Option<bool>, /* if present indicates whether the roll is accessible*/
@@ -51,65 +60,52 @@ enum Tile {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
-struct Floor {
-    tiles: Vec<Vec<Tile>>,
+pub struct Floor {
+    tiles: Grid<Tile>,
     accessible_slots: Vec<(usize, usize)>,
 }
 
 impl Floor {
-    fn get(&self, row: usize, col: usize) -> Option<&Tile> {
-        self.tiles.get(row).and_then(|r| r.get(col))
+    pub fn accessible_slots(&self) -> &[(usize, usize)] {
+        &self.accessible_slots
     }
 
-    fn iter_neighbours(
+    pub fn tiles(&self) -> &Grid<Tile> {
+        &self.tiles
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&Tile> {
+        self.tiles.get(row, col)
+    }
+
+    pub fn iter_neighbours(
         &self,
         row: usize,
         col: usize,
     ) -> impl Iterator<Item = ((usize, usize), Tile)> + '_ {
-        let directions = [
-            (-1, -1),
-            (-1, 0),
-            (-1, 1),
-            (0, -1),
-            (0, 1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-        ];
-
-        directions.into_iter().filter_map(move |(dr, dc)| {
-            let new_row = row as isize + dr;
-            let new_col = col as isize + dc;
-
-            if new_row >= 0 && new_col >= 0 {
-                self.get(new_row as usize, new_col as usize)
-                    .map(|tile| ((new_row as usize, new_col as usize), *tile))
-            } else {
-                None
-            }
-        })
+        self.tiles
+            .neighbours8(row, col)
+            .map(|position| (position, self.tiles[position]))
     }
 
-    fn process_accessible(&mut self) -> u32 {
+    pub fn process_accessible(&mut self) -> u32 {
         self.accessible_slots.clear();
 
-        for row in 0..self.tiles.len() {
-            for col in 0..self.tiles[row].len() {
-                if matches!(self.tiles[row][col], Tile::PaperRoll { accessible: _ }) {
-                    let non_empty_neighbours = self
-                        .iter_neighbours(row, col)
-                        .filter(|(_, inner_tile)| *inner_tile != Tile::EmptySpace)
-                        .count();
-
-                    self.tiles[row][col] = if non_empty_neighbours < 4 {
-                        self.accessible_slots.push((row, col));
-                        Tile::PaperRoll {
-                            accessible: Some(true),
-                        }
-                    } else {
-                        Tile::PaperRoll {
-                            accessible: Some(false),
-                        }
+        for (row, col) in self.tiles.positions() {
+            if matches!(self.tiles[(row, col)], Tile::PaperRoll { accessible: _ }) {
+                let non_empty_neighbours = self
+                    .iter_neighbours(row, col)
+                    .filter(|(_, inner_tile)| *inner_tile != Tile::EmptySpace)
+                    .count();
+
+                self.tiles[(row, col)] = if non_empty_neighbours < 4 {
+                    self.accessible_slots.push((row, col));
+                    Tile::PaperRoll {
+                        accessible: Some(true),
+                    }
+                } else {
+                    Tile::PaperRoll {
+                        accessible: Some(false),
                     }
                 }
             }
@@ -117,69 +113,62 @@ impl Floor {
         self.accessible_slots.len() as u32
     }
 
-    fn clear_acessible(&mut self) {
-        for (row, col) in &self.accessible_slots {
-            self.tiles[*row][*col] = Tile::EmptySpace;
+    pub fn clear_acessible(&mut self) {
+        for &position in &self.accessible_slots {
+            self.tiles[position] = Tile::EmptySpace;
         }
         self.accessible_slots.clear();
     }
+
+    /// Removes the accessible rolls from a copy of the floor until none are left, returning the
+    /// rolls removed in each wave.
+    pub fn removal_waves(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut floor = self.clone();
+        let mut waves = Vec::new();
+        while floor.process_accessible() > 0 {
+            waves.push(floor.accessible_slots.clone());
+            floor.clear_acessible();
+        }
+        waves
+    }
 }
 
 impl fmt::Display for Floor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in &self.tiles {
-            for tile in row {
-                let c = match tile {
-                    Tile::EmptySpace => '.',
-                    Tile::PaperRoll {
-                        accessible: Some(true),
-                    } => 'x',
-                    Tile::PaperRoll { accessible: _ } => '@',
-                };
-                write!(f, "{}", c)?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        write!(f, "{}", self.tiles)
     }
 }
 
-fn parse_floor_line(input: &str) -> IResult<&str, Vec<Tile>> {
-    fn parse_tile(input: &str) -> IResult<&str, Tile> {
-        let (input, c) = one_of(".@x")(input)?;
-        let tile = match c {
-            '.' => Tile::EmptySpace,
-            '@' => Tile::PaperRoll { accessible: None },
-            'x' => Tile::PaperRoll {
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Tile::EmptySpace => '.',
+            Tile::PaperRoll {
                 accessible: Some(true),
-            },
-            _ => unreachable!(),
+            } => 'x',
+            Tile::PaperRoll { accessible: _ } => '@',
         };
-        Ok((input, tile))
+        write!(f, "{}", c)
+    }
+}
+
+pub fn parse_tile(c: char) -> Option<Tile> {
+    match c {
+        '.' => Some(Tile::EmptySpace),
+        '@' => Some(Tile::PaperRoll { accessible: None }),
+        'x' => Some(Tile::PaperRoll {
+            accessible: Some(true),
+        }),
+        _ => None,
     }
-    many1(parse_tile)(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn test_data() -> Vec<Vec<Tile>> {
-        [
-            "..@@.@@@@.",
-            "@@@.@.@.@@",
-            "@@@@@.@.@@",
-            "@.@@@@..@.",
-            "@@.@@@@.@@",
-            ".@@@@@@@.@",
-            ".@.@.@.@@@",
-            "@.@@@.@@@@",
-            ".@@@@@@@@.",
-            "@.@.@@@.@.",
-        ]
-        .iter()
-        .map(|s| parse_floor_line(s).unwrap().1)
-        .collect()
+    fn test_data() -> Grid<Tile> {
+        Grid::parse(EXAMPLE, parse_tile).unwrap()
     }
 
     #[test]
@@ -197,9 +186,14 @@ mod tests {
         )];
 
         for (input, expected) in test_cases {
-            let (remaining, tiles) = parse_floor_line(input).unwrap();
-            assert_eq!(remaining, "", "Should consume entire input");
-            assert_eq!(tiles, expected, "Failed for input: {}", input);
+            let floor = parse_floor(input).unwrap();
+            assert_eq!(floor.tiles().height(), 1, "Should be a single row");
+            assert_eq!(
+                floor.tiles().row(0),
+                expected,
+                "Failed for input: {}",
+                input
+            );
         }
     }
 
@@ -210,8 +204,8 @@ mod tests {
             tiles,
             accessible_slots: Vec::new(),
         };
-        let max_row = floor.tiles.len() - 1;
-        let max_col = floor.tiles[0].len() - 1;
+        let max_row = floor.tiles.height() - 1;
+        let max_col = floor.tiles.width() - 1;
 
         // Test top-left corner (0, 0)
         let neighbours_top_left: Vec<_> = floor.iter_neighbours(0, 0).collect();
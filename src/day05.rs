@@ -1,41 +1,75 @@
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Example, Solution};
 use nom::{
     IResult,
     character::complete::{char, digit1, line_ending},
     combinator::map_res,
+    error::ErrorKind,
     multi::separated_list1,
     sequence::separated_pair,
 };
 use rangemap::RangeSet;
-use std::fs;
-
-pub fn solve_problem_1(main_file: &str) -> std::io::Result<()> {
-    let db = read_database_file(main_file)?;
-    println!(
-        "problem 1: fresh ingredients in database: {}",
-        db.current_fresh_ingredient_count()
-    );
-    Ok(())
-}
+use std::fmt;
+
+pub struct Day05;
+
+const EXAMPLE: &str = "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32";
+
+impl Solution for Day05 {
+    type Input = Database;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some(Answer::Int(3)),
+        part2: Some(Answer::Int(14)),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_database(input)
+            .map(|(_, db)| db)
+            .map_err(|e| match e {
+                nom::Err::Error(e) | nom::Err::Failure(e) if e.code == ErrorKind::Verify => {
+                    Error::parse_at(input, e.input, "the range ends before it starts")
+                }
+                nom::Err::Error(e) | nom::Err::Failure(e) if e.code == ErrorKind::TooLarge => {
+                    Error::parse_at(
+                        input,
+                        e.input,
+                        "the range ends past the largest id, u64::MAX - 1",
+                    )
+                }
+                e => Error::from_nom(input, e),
+            })
+    }
 
-pub fn solve_problem_2(main_file: &str) -> std::io::Result<()> {
-    let db = read_database_file(main_file)?;
-    println!(
-        "problem 2: count of unique fresh ingredient ids in the ranges: {}",
-        db.unique_fresh_ingredient_count()
-    );
-    Ok(())
+    fn part1(db: &Self::Input) -> Result<u64> {
+        Ok(db.current_fresh_ingredient_count())
+    }
+
+    fn part2(db: &Self::Input) -> Result<u64> {
+        Ok(db.unique_fresh_ingredient_count())
+    }
 }
 
-struct Database {
+#[derive(Debug, PartialEq)]
+pub struct Database {
     fresh_ranges: RangeSet<u64>,
     ingredients: Vec<u64>,
 }
 
 impl Database {
-    fn from(ranges: Vec<(u64, u64)>, values: Vec<u64>) -> Self {
+    /// Builds a database from inclusive ranges, which must end before `u64::MAX` (the parser
+    /// rejects ones that don't).
+    pub fn from(ranges: Vec<(u64, u64)>, values: Vec<u64>) -> Self {
         let mut rs = RangeSet::new();
         for range in ranges {
-            let r = range.0..range.1 + 1;
+            let end = range
+                .1
+                .checked_add(1)
+                .expect("the range ends before u64::MAX");
+            let r = range.0..end;
             rs.insert(r);
         }
         Database {
@@ -44,30 +78,57 @@ impl Database {
         }
     }
 
-    fn is_fresh(&self, ingredient: &u64) -> bool {
+    pub fn is_fresh(&self, ingredient: &u64) -> bool {
         self.fresh_ranges.contains(ingredient)
     }
 
-    fn current_fresh_ingredient_count(&self) -> u64 {
+    pub fn current_fresh_ingredient_count(&self) -> u64 {
         self.ingredients
             .iter()
             .filter(|id| self.is_fresh(id))
             .count() as u64
     }
 
-    fn unique_fresh_ingredient_count(&self) -> u64 {
-        self.fresh_ranges.iter().map(|r| r.end - r.start + 1).sum()
+    pub fn unique_fresh_ingredient_count(&self) -> u64 {
+        // the ranges are half-open, `from` stores `low..high + 1`
+        self.fresh_ranges.iter().map(|r| r.end - r.start).sum()
+    }
+}
+
+/// Prints the database in the puzzle format. Overlapping ranges were merged when the database was
+/// built, so they come out as a single range.
+impl fmt::Display for Database {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in self.fresh_ranges.iter() {
+            writeln!(f, "{}-{}", r.start, r.end - 1)?;
+        }
+        writeln!(f)?;
+        for ingredient in &self.ingredients {
+            writeln!(f, "{}", ingredient)?;
+        }
+        Ok(())
     }
 }
 
 // TODO implement a streaming parser
-fn parse_database(input: &str) -> IResult<&str, Database> {
+pub fn parse_database(input: &str) -> IResult<&str, Database> {
     fn parse_u64(input: &str) -> IResult<&str, u64> {
         map_res(digit1, str::parse)(input)
     }
 
     fn parse_range(input: &str) -> IResult<&str, (u64, u64)> {
-        separated_pair(parse_u64, char('-'), parse_u64)(input)
+        // failures rather than errors, so the list of ranges doesn't just end here
+        let fail = |kind| nom::Err::Failure(nom::error::Error::new(input, kind));
+
+        let (rest, (low, high)) = separated_pair(parse_u64, char('-'), parse_u64)(input)?;
+        if low > high {
+            return Err(fail(ErrorKind::Verify));
+        }
+        // the ranges are stored half-open, the end must leave room for one past it
+        if high.checked_add(1).is_none() {
+            return Err(fail(ErrorKind::TooLarge));
+        }
+        Ok((rest, (low, high)))
     }
     let (input, ranges) = separated_list1(line_ending, parse_range)(input)?;
     let (input, _) = line_ending(input)?;
@@ -77,16 +138,10 @@ fn parse_database(input: &str) -> IResult<&str, Database> {
     Ok((input, Database::from(ranges, ingredients)))
 }
 
-fn read_database_file(file_path: &str) -> std::io::Result<Database> {
-    let content = fs::read_to_string(file_path)?;
-    parse_database(&content)
-        .map(|(_, db)| db)
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_database_fresh_ranges() {
@@ -99,10 +154,22 @@ mod tests {
         assert!(!db.is_fresh(&6), "6 should not be fresh");
     }
 
+    #[test]
+    fn test_unique_fresh_ingredient_count() {
+        let count = |ranges| Database::from(ranges, vec![]).unique_fresh_ingredient_count();
+
+        assert_eq!(count(vec![(4, 4)]), 1);
+        // overlapping ranges merge into 10-18
+        assert_eq!(count(vec![(10, 14), (12, 18)]), 9);
+        // adjacent ranges merge into 3-7
+        assert_eq!(count(vec![(3, 5), (6, 7)]), 5);
+        // the example from the puzzle text
+        assert_eq!(count(vec![(3, 5), (10, 14), (16, 20), (12, 18)]), 14);
+    }
+
     #[test]
     fn test_parse_database() {
-        let input = "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32";
-        let (remaining, db) = parse_database(input).unwrap();
+        let (remaining, db) = parse_database(EXAMPLE).unwrap();
 
         assert_eq!(remaining, "", "Should consume entire input");
         assert_eq!(db.ingredients, vec![1, 5, 8, 11, 17, 32]);
@@ -117,4 +184,52 @@ mod tests {
         assert!(!db.is_fresh(&8), "8 should not be fresh");
         assert!(!db.is_fresh(&32), "32 should not be fresh");
     }
+
+    #[test]
+    fn test_parse_reversed_range() {
+        let err = Day05::parse("3-5\n5-0\n\n1").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 1: the range ends before it starts"
+        );
+    }
+
+    #[test]
+    fn test_parse_range_ending_at_u64_max() {
+        let err = Day05::parse("3-5\n0-18446744073709551615\n\n1")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 1: the range ends past the largest id, u64::MAX - 1"
+        );
+
+        let db = Day05::parse("0-18446744073709551614\n\n1").unwrap();
+        assert_eq!(Day05::part2(&db).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_display_database() {
+        let db = Database::from(vec![(3, 5), (10, 14), (12, 18)], vec![1, 5]);
+        assert_eq!(db.to_string(), "3-5\n10-18\n\n1\n5\n");
+    }
+
+    proptest! {
+        #[test]
+        fn test_database_round_trip(
+            ranges in prop::collection::vec((0u64..1_000_000, 0u64..1_000), 1..20),
+            ingredients in prop::collection::vec(0u64..1_001_000, 1..20),
+            crlf: bool,
+        ) {
+            let ranges = ranges.into_iter().map(|(low, width)| (low, low + width)).collect();
+            let db = Database::from(ranges, ingredients);
+
+            let mut printed = db.to_string();
+            if crlf {
+                printed = printed.replace('\n', "\r\n");
+            }
+            let (_, parsed) = parse_database(&printed).unwrap();
+            prop_assert_eq!(parsed, db);
+        }
+    }
 }
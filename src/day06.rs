@@ -0,0 +1,51 @@
+// Day 6 was solved twice over with two different readers of the same worksheet: day6a parses it
+// row by row with nom, day6b walks it column by column. This glues them into a single day.
+
+use crate::day6a::{self, Problem};
+use crate::day6b::{self, Op};
+use crate::error::Result;
+use crate::input::Normalize;
+use crate::solution::{Answer, Example, Solution};
+
+pub struct Day06;
+
+// the operand rows are padded to the same width, the column reader relies on it
+const EXAMPLE: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
+
+pub struct Worksheet {
+    problems: Vec<Problem>,
+    columns: Vec<(u64, Option<Op>)>,
+}
+
+impl Solution for Day06 {
+    type Input = Worksheet;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some(Answer::Int(4277556)),
+        part2: Some(Answer::Int(3263827)),
+    }];
+
+    // the column reader needs the padding at the end of the rows
+    const NORMALIZE: Normalize = Normalize {
+        trailing_whitespace: false,
+        ..Normalize::ALL
+    };
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Worksheet {
+            problems: day6a::read_worksheet(input)?,
+            columns: day6b::read_columns(input)?,
+        })
+    }
+
+    fn part1(worksheet: &Self::Input) -> Result<u64> {
+        Ok(day6a::solve_problem_1(&worksheet.problems))
+    }
+
+    fn part2(worksheet: &Self::Input) -> Result<u64> {
+        Ok(day6b::solve_problem_2(&worksheet.columns))
+    }
+}
@@ -1,35 +1,73 @@
+use crate::error::{Error, Result};
+use crate::grid;
+use crate::solution::{Answer, Example, Solution, Strategy};
 use std::collections::HashSet;
 use std::fmt;
 
-pub fn solve_problem_1(main_file: &str) -> std::io::Result<()> {
-    // read the file into a grid and then count the splits and print them out
+pub struct Day07;
 
-    let input = std::fs::read_to_string(main_file)?;
-    let mut grid = Grid::parse_grid(&input);
-    grid.trace_tachyons_down();
-    println!("problem 1: tachyons split times: {}", grid.split_count);
-
-    // Implementation goes here
-    Ok(())
-}
+const EXAMPLE: &str = "\
+.......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............";
 
-pub fn solve_problem_2(main_file: &str) -> std::io::Result<()> {
-    // read the file into a grid and then count the splits and print them out
+impl Solution for Day07 {
+    type Input = Grid;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some(Answer::Int(21)),
+        part2: Some(Answer::Int(40)),
+    }];
+
+    // enumerating the paths is exponential, it only finishes on small grids
+    const PART2_STRATEGIES: &'static [Strategy<Self::Input, u64>] = &[
+        Strategy {
+            name: "dynamic-programming",
+            solve: Self::part2,
+        },
+        Strategy {
+            name: "enumerate-paths",
+            solve: |grid| Ok(grid.trace_all_paths().len() as u64),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Grid::parse_grid(input)
+    }
 
-    let input = std::fs::read_to_string(main_file)?;
-    let grid = Grid::parse_grid(&input);
-    let path_count = grid.count_all_paths();
-    println!("problem 2: all possible tachyon paths: {}", path_count);
+    fn part1(grid: &Self::Input) -> Result<u32> {
+        let mut grid = grid.clone();
+        grid.trace_tachyons_down()?;
+        Ok(grid.split_count)
+    }
 
-    // Implementation goes here
-    Ok(())
+    fn part2(grid: &Self::Input) -> Result<u64> {
+        Ok(grid.count_all_paths())
+    }
 }
 
-#[derive(Hash, PartialEq, Eq)]
+#[derive(Clone, Hash, PartialEq, Eq)]
 enum Direction {
     Up,
 }
 
+#[derive(Clone)]
 enum SquareType {
     Source,
     EmptySpace,
@@ -46,91 +84,129 @@ impl SquareType {
     }
 }
 
+#[derive(Clone)]
 struct Square {
     square_type: SquareType,
     visited_from: HashSet<Direction>,
 }
 
-struct Grid {
-    squares: Vec<Vec<Square>>,
+#[derive(Clone)]
+pub struct Grid {
+    squares: grid::Grid<Square>,
     split_count: u32,
 }
 
 impl Grid {
-    fn parse_grid(input: &str) -> Grid {
-        fn parse_square(c: char) -> SquareType {
-            match c {
-                'S' => SquareType::Source,
-                '^' => SquareType::Splitter,
-                '.' => SquareType::EmptySpace,
-                _ => panic!("Unknown character: {}", c),
-            }
-        }
+    pub fn split_count(&self) -> u32 {
+        self.split_count
+    }
 
-        let mut sourches = Vec::new();
-        let mut squares = Vec::new();
+    pub fn height(&self) -> usize {
+        self.squares.height()
+    }
 
-        for (row_idx, line) in input.lines().enumerate() {
-            let mut row = Vec::new();
-            for (col_idx, c) in line.chars().enumerate() {
-                let square_type = parse_square(c);
-                let mut directions = HashSet::new();
+    pub fn width(&self) -> usize {
+        self.squares.width()
+    }
 
-                if matches!(square_type, SquareType::Source) {
-                    sourches.push((row_idx, col_idx));
-                    directions.insert(Direction::Up);
-                }
+    /// The character the square was parsed from.
+    pub fn glyph(&self, row: usize, col: usize) -> char {
+        self.squares[(row, col)].square_type.to_char()
+    }
 
-                row.push(Square {
-                    square_type,
-                    visited_from: directions,
-                });
+    pub fn has_beam(&self, row: usize, col: usize) -> bool {
+        self.squares[(row, col)]
+            .visited_from
+            .contains(&Direction::Up)
+    }
+
+    pub fn parse_grid(input: &str) -> Result<Grid> {
+        fn parse_square(c: char) -> Option<SquareType> {
+            match c {
+                'S' => Some(SquareType::Source),
+                '^' => Some(SquareType::Splitter),
+                '.' => Some(SquareType::EmptySpace),
+                _ => None,
             }
-            squares.push(row);
         }
 
-        Grid {
+        let squares = grid::Grid::parse(input, |c| {
+            let square_type = parse_square(c)?;
+            let mut directions = HashSet::new();
+
+            if matches!(square_type, SquareType::Source) {
+                directions.insert(Direction::Up);
+            }
+
+            Some(Square {
+                square_type,
+                visited_from: directions,
+            })
+        })?;
+
+        Ok(Grid {
             squares,
             split_count: 0,
-        }
+        })
     }
 
     // trace out the beams downward from the sourches
-    fn trace_tachyons_down(&mut self) {
+    pub fn trace_tachyons_down(&mut self) -> Result<()> {
         // this is n^2. but whatever, we could trace down from the sources since it can be captured during
         // parsing.
-        for i in 1..self.squares.len() {
-            for j in 0..self.squares[i].len() {
-                let has_beam_above = self.squares[i - 1][j].visited_from.contains(&Direction::Up);
-                if has_beam_above {
-                    match self.squares[i][j].square_type {
-                        SquareType::EmptySpace => {
-                            self.squares[i][j].visited_from.insert(Direction::Up);
+        for i in 1..self.squares.height() {
+            self.trace_row(i)?;
+        }
+        Ok(())
+    }
+
+    // moves the beams from row `i - 1` down into row `i`, rows are traced top to bottom from 1
+    pub fn trace_row(&mut self, i: usize) -> Result<()> {
+        for j in 0..self.squares.width() {
+            let has_beam_above = self.squares[(i - 1, j)]
+                .visited_from
+                .contains(&Direction::Up);
+            if has_beam_above {
+                match self.squares[(i, j)].square_type {
+                    SquareType::EmptySpace => {
+                        self.squares[(i, j)].visited_from.insert(Direction::Up);
+                    }
+                    SquareType::Splitter => {
+                        // I am modelling things as the splitter diverting beams to left and
+                        // right, but the go downwards "magically". The splitter doesn't count
+                        // as being visited. This can be remodelled later by making the
+                        // has_beam_above check more complex.
+                        if j == 0 || j + 1 >= self.squares.width() {
+                            return Err(Error::InvalidInput(format!(
+                                "a splitter should not be on the edge of the grid, found one at line {}",
+                                i + 1
+                            )));
                         }
-                        SquareType::Splitter => {
-                            // I am modelling things as the splitter diverting beams to left and
-                            // right, but the go downwards "magically". The splitter doesn't count
-                            // as being visited. This can be remodelled later by making the
-                            // has_beam_above check more complex.
-                            if j == 0 {
-                                panic!("a splitter should not be at idx 0")
-                            }
-                            if !self.squares[i][j - 1].visited_from.contains(&Direction::Up) {
-                                self.squares[i][j - 1].visited_from.insert(Direction::Up);
-                            }
-                            if !self.squares[i][j + 1].visited_from.contains(&Direction::Up) {
-                                self.squares[i][j + 1].visited_from.insert(Direction::Up);
-                            }
-
-                            self.split_count += 1;
+                        if !self.squares[(i, j - 1)]
+                            .visited_from
+                            .contains(&Direction::Up)
+                        {
+                            self.squares[(i, j - 1)].visited_from.insert(Direction::Up);
                         }
-                        SquareType::Source => {
-                            panic!("a source was not expected here")
+                        if !self.squares[(i, j + 1)]
+                            .visited_from
+                            .contains(&Direction::Up)
+                        {
+                            self.squares[(i, j + 1)].visited_from.insert(Direction::Up);
                         }
+
+                        self.split_count += 1;
+                    }
+                    SquareType::Source => {
+                        return Err(Error::InvalidInput(format!(
+                            "a source was not expected below a beam, found one at line {}",
+                            i + 1
+                        )));
                     }
                 }
             }
         }
+        Ok(())
     }
 
     // notes:
@@ -141,16 +217,34 @@ impl Grid {
     //  a. the algorithm is not tracing rays bottom up ! The nodes are being visited left to right bottom-up, but not tracing!
 
     // Count all unique paths using dynamic programming (bottom-up)
-    fn count_all_paths(&self) -> u64 {
-        let rows = self.squares.len();
-        let cols = self.squares[0].len();
+    pub fn count_all_paths(&self) -> u64 {
+        let dp = self.path_counts();
 
-        // dp[row][col] = number of paths from (row, col) to bottom
-        let mut dp: Vec<Vec<u64>> = vec![vec![0; cols]; rows];
+        // Sum up paths from all source positions
+        let mut total_paths = 0;
+        for (position, square) in self.squares.iter() {
+            if matches!(square.square_type, SquareType::Source) {
+                total_paths += dp[position];
+            }
+        }
+
+        total_paths
+    }
+
+    /// The table behind `count_all_paths`: the number of paths from each square to the bottom row.
+    pub fn path_counts(&self) -> grid::Grid<u64> {
+        let rows = self.squares.height();
+        let cols = self.squares.width();
+
+        // dp[(row, col)] = number of paths from (row, col) to bottom
+        let mut dp = grid::Grid::new(cols, rows, 0);
+        if rows == 0 {
+            return dp;
+        }
 
         // Base case: bottom row - each position has exactly 1 path (stay there)
         (0..cols).for_each(|col| {
-            dp[rows - 1][col] = 1;
+            dp[(rows - 1, col)] = 1;
         });
 
         // Work backwards from second-to-last row to top
@@ -160,10 +254,10 @@ impl Grid {
 
                 // Only compute paths from positions that can be reached
                 // (we'll filter by actual reachability later, but for now compute all)
-                match self.squares[next_row][col].square_type {
+                match self.squares[(next_row, col)].square_type {
                     SquareType::EmptySpace | SquareType::Source => {
                         // Path continues straight down
-                        dp[row][col] = dp[next_row][col];
+                        dp[(row, col)] = dp[(next_row, col)];
                     }
                     SquareType::Splitter => {
                         // Path splits into left and right
@@ -171,48 +265,35 @@ impl Grid {
 
                         // Right branch
                         if col + 1 < cols {
-                            paths += dp[next_row][col + 1];
+                            paths += dp[(next_row, col + 1)];
                         }
 
                         // Left branch
                         if col > 0 {
-                            paths += dp[next_row][col - 1];
+                            paths += dp[(next_row, col - 1)];
                         }
 
-                        dp[row][col] = paths;
+                        dp[(row, col)] = paths;
                     }
                 }
             }
         }
 
-        // Sum up paths from all source positions
-        let mut total_paths = 0;
-        for (row_idx, row) in self.squares.iter().enumerate() {
-            for (col_idx, square) in row.iter().enumerate() {
-                if matches!(square.square_type, SquareType::Source) {
-                    total_paths += dp[row_idx][col_idx];
-                }
-            }
-        }
-
-        total_paths
+        dp
     }
 
     // OLD: Trace all unique paths using explicit stack (no recursion)
     // This is exponential and infeasible for large inputs
-    #[allow(dead_code)]
-    fn trace_all_paths(&self) -> HashSet<String> {
+    pub fn trace_all_paths(&self) -> HashSet<String> {
         let mut all_paths = HashSet::new();
 
         // Stack holds: (row, col, path_so_far)
         let mut stack: Vec<(usize, usize, Vec<usize>)> = Vec::new();
 
         // Find all sources and push them onto the stack
-        for (row_idx, row) in self.squares.iter().enumerate() {
-            for (col_idx, square) in row.iter().enumerate() {
-                if matches!(square.square_type, SquareType::Source) {
-                    stack.push((row_idx, col_idx, vec![]));
-                }
+        for ((row_idx, col_idx), square) in self.squares.iter() {
+            if matches!(square.square_type, SquareType::Source) {
+                stack.push((row_idx, col_idx, vec![]));
             }
         }
 
@@ -222,7 +303,7 @@ impl Grid {
             path.push(col);
 
             // If we've reached the bottom row, save the path
-            if row == self.squares.len() - 1 {
+            if row == self.squares.height() - 1 {
                 let path_string = path
                     .iter()
                     .map(|c| c.to_string())
@@ -235,7 +316,7 @@ impl Grid {
 
             // Check what's below us
             let next_row = row + 1;
-            match self.squares[next_row][col].square_type {
+            match self.squares[(next_row, col)].square_type {
                 SquareType::EmptySpace | SquareType::Source => {
                     // Continue straight down
                     stack.push((next_row, col, path));
@@ -244,7 +325,7 @@ impl Grid {
                     // Split: push both left and right branches onto stack
 
                     // Push right branch (col + 1)
-                    if col + 1 < self.squares[next_row].len() {
+                    if col + 1 < self.squares.width() {
                         stack.push((next_row, col + 1, path.clone()));
                     }
 
@@ -269,15 +350,15 @@ impl Grid {
     // }
 }
 
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.square_type.to_char())
+    }
+}
+
 impl fmt::Display for Grid {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in &self.squares {
-            for square in row {
-                write!(f, "{}", square.square_type.to_char())?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        write!(f, "{}", self.squares)
     }
 }
 
@@ -286,49 +367,19 @@ mod tests {
     use super::*;
 
     fn test_grid_fixture() -> Grid {
-        let input = "\
-.......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............";
-        Grid::parse_grid(input)
+        Grid::parse_grid(EXAMPLE).unwrap()
     }
 
     #[test]
     fn test_sample_grid() {
         let mut grid = test_grid_fixture();
-        println!("{}", grid);
-
-        grid.trace_tachyons_down();
-
-        // println!(
-        //     "After tracing tachyons down: {}",
-        //     grid.tachyons_in_bottom_row()
-        // );
-
-        println!("tachoyn splits: {}", grid.split_count);
+        grid.trace_tachyons_down().unwrap();
+        assert_eq!(grid.split_count, 21);
     }
 
     #[test]
     fn test_all_paths() {
         let grid = test_grid_fixture();
-        let paths = grid.trace_all_paths();
-
-        println!("Total unique paths: {}", paths.len());
-        for path in &paths {
-            println!("  {}", path);
-        }
+        assert_eq!(grid.trace_all_paths().len(), 40);
     }
 }
@@ -5,69 +5,74 @@ use std::str::FromStr;
 
 use indexmap::IndexMap;
 
+use crate::error::{Error, Result};
+use crate::geometry::Point3D;
+use crate::solution::{Answer, Example, Solution};
+
 // Fixes
 // * The IndexMap and sorting by x-coordinate were used earlier for a threshold-based approach.
 //   This dependency is no longer needed, so the sorting step and IndexMap usage can be removed.
 
+pub struct Day08;
 
-pub fn solve_problem_1(main_file: &str) -> std::io::Result<()> {
-    let input = std::fs::read_to_string(main_file)?;
-
-    let mut system: LightingSystem = input.parse().map_err(|e| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!("Parse error: {}", e),
-        )
-    })?;
-
-    system.connect_junctions_n(1000);
-
-    let mut sizes: Vec<u32> = system
-        .unique_circuits()
-        .map(|circuit| circuit.borrow().len() as u32)
-        .collect();
-    sizes.sort_by(|a, b| b.cmp(a)); // Sort descending
-    let prod: u32 = sizes.iter().take(3).product();
-
-    println!("problem 1: result = {}", prod);
-    Ok(())
-}
+const EXAMPLE: &str = "\
+162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689";
 
-pub fn solve_problem_2(main_file: &str) -> std::io::Result<()> {
-    let input = std::fs::read_to_string(main_file)?;
-
-    let mut system: LightingSystem = input.parse().map_err(|e| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!("Parse error: {}", e),
-        )
-    })?;
-
-    if let Some((a, b)) = system.connect_into_single_circuit() {
-        println!("problem 2: result = {}", a.x as u64 * b.x as u64);
-        Ok(())
-    } else {
-        panic!("boom");
+impl Solution for Day08 {
+    // The lighting system is mutated as circuits are merged, so each part builds its own.
+    type Input = Vec<Point3D>;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        // part 1 makes 1000 connections, the puzzle works the example with 10
+        part1: None,
+        part2: Some(Answer::Int(25272)),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_points(input)
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point3D {
-    x: i32,
-    y: i32,
-    z: i32,
-}
+    fn part1(points: &Self::Input) -> Result<u32> {
+        let mut system = LightingSystem::from_points(points.clone());
+        system.connect_junctions_n(1000);
 
-impl Point3D {
-    fn new(x: i32, y: i32, z: i32) -> Self {
-        Point3D { x, y, z }
+        let mut sizes: Vec<u32> = system
+            .unique_circuits()
+            .map(|circuit| circuit.borrow().len() as u32)
+            .collect();
+        sizes.sort_by(|a, b| b.cmp(a)); // Sort descending
+        Ok(sizes.iter().take(3).product())
     }
 
-    fn distance_squared(&self, other: &Point3D) -> u64 {
-        let dx = (self.x - other.x) as i64;
-        let dy = (self.y - other.y) as i64;
-        let dz = (self.z - other.z) as i64;
-        (dx * dx + dy * dy + dz * dz) as u64
+    fn part2(points: &Self::Input) -> Result<u64> {
+        let mut system = LightingSystem::from_points(points.clone());
+
+        let (a, b) = system.connect_into_single_circuit().ok_or_else(|| {
+            Error::NoSolution("the junction boxes never form a single circuit".to_string())
+        })?;
+        Ok(a.x as u64 * b.x as u64)
     }
 }
 
@@ -81,33 +86,47 @@ impl Point3D {
 ///
 /// Traditional union-find uses parent pointers and path compression. This uses shared ownership
 /// through Rc, which makes "find" O(1) but "union" O(smaller_set_size) due to pointer updates.
-struct LightingSystem {
+pub struct LightingSystem {
     circuits: IndexMap<Point3D, Rc<RefCell<HashSet<Point3D>>>>,
 }
 
+pub fn parse_points(s: &str) -> Result<Vec<Point3D>> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let parts: Vec<i32> = line
+                .split(',')
+                .map(|p| {
+                    let p = p.trim();
+                    p.parse::<i32>().map_err(|e| {
+                        Error::parse_at(s, p, format!("Failed to parse number: {}", e))
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            if parts.len() != 3 {
+                return Err(Error::parse_at(
+                    s,
+                    line,
+                    format!("Expected 3 coordinates, got {}", parts.len()),
+                ));
+            }
+
+            Ok(Point3D::new(parts[0], parts[1], parts[2]))
+        })
+        .collect()
+}
+
 impl FromStr for LightingSystem {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // Parse all points
-        let mut points: Vec<Point3D> = s
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .map(|line| {
-                let parts: Vec<i32> = line
-                    .split(',')
-                    .map(|p| p.trim().parse::<i32>())
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(|e| format!("Failed to parse number: {}", e))?;
-
-                if parts.len() != 3 {
-                    return Err(format!("Expected 3 coordinates, got {}", parts.len()));
-                }
+    type Err = Error;
 
-                Ok(Point3D::new(parts[0], parts[1], parts[2]))
-            })
-            .collect::<Result<Vec<_>, String>>()?;
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(LightingSystem::from_points(parse_points(s)?))
+    }
+}
 
+impl LightingSystem {
+    pub fn from_points(mut points: Vec<Point3D>) -> Self {
         // Sort by x coordinate (ascending)
         points.sort_by_key(|p| p.x);
 
@@ -116,12 +135,10 @@ impl FromStr for LightingSystem {
         for point in points {
             circuits.insert(point, Rc::new(RefCell::new(HashSet::from([point]))));
         }
-        Ok(LightingSystem { circuits })
+        LightingSystem { circuits }
     }
-}
 
-impl LightingSystem {
-    fn unique_circuits(&self) -> impl Iterator<Item = Rc<RefCell<HashSet<Point3D>>>> + '_ {
+    pub fn unique_circuits(&self) -> impl Iterator<Item = Rc<RefCell<HashSet<Point3D>>>> + '_ {
         let mut seen = HashSet::new();
         self.circuits
             .values()
@@ -129,7 +146,6 @@ impl LightingSystem {
             .cloned()
     }
 
-    
     /// Union-Find "union" operation: merges two disjoint sets into one.
     ///
     /// This implements union-by-size heuristic for efficiency - the smaller set is merged
@@ -137,7 +153,7 @@ impl LightingSystem {
     /// we update all points in the smaller set to point to the larger set's shared HashSet.
     ///
     /// Returns true if a merge happened, false if the points were already in the same circuit.
-    fn merge_circuits(&mut self, point_a: &Point3D, point_b: &Point3D) -> bool {
+    pub fn merge_circuits(&mut self, point_a: &Point3D, point_b: &Point3D) -> bool {
         // Union-Find "find" operation (implicit): get the Rc pointer representing each set.
         // The pointer identity IS the set identity - no path compression needed.
         let set_a_rc = self.circuits.get(point_a).unwrap().clone();
@@ -167,7 +183,7 @@ impl LightingSystem {
         true
     }
 
-    fn connect_junctions_n(&mut self, wire_count: u32) -> u32 {
+    pub fn connect_junctions_n(&mut self, wire_count: u32) -> u32 {
         let points: Vec<Point3D> = self.circuits.keys().copied().collect();
         let pairs = Self::sorted_pairs(&points);
 
@@ -184,7 +200,7 @@ impl LightingSystem {
     }
 
     // Pre-compute all pairs with distances
-    fn sorted_pairs(points: &Vec<Point3D>) -> Vec<(Point3D, Point3D, u64)> {
+    fn sorted_pairs(points: &[Point3D]) -> Vec<(Point3D, Point3D, u64)> {
         let mut pairs: Vec<(Point3D, Point3D, u64)> = Vec::new();
 
         for i in 0..points.len() {
@@ -200,7 +216,7 @@ impl LightingSystem {
         pairs
     }
 
-    fn connect_into_single_circuit(&mut self) -> Option<(Point3D, Point3D)> {
+    pub fn connect_into_single_circuit(&mut self) -> Option<(Point3D, Point3D)> {
         // Pre-compute all pairs with distances
         let points: Vec<Point3D> = self.circuits.keys().copied().collect();
         let pairs: Vec<(Point3D, Point3D, u64)> = Self::sorted_pairs(&points);
@@ -224,31 +240,9 @@ impl LightingSystem {
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = "\
-162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689";
-
     #[test]
     fn test_sample_problem_1() {
-        let mut system: LightingSystem = TEST_INPUT.parse().unwrap();
+        let mut system: LightingSystem = EXAMPLE.parse().unwrap();
         let connections_made = system.connect_junctions_n(10);
         assert_eq!(connections_made, 10);
 
@@ -270,7 +264,7 @@ mod tests {
 
     #[test]
     fn test_sample_problem_2() {
-        let mut system: LightingSystem = TEST_INPUT.parse().unwrap();
+        let mut system: LightingSystem = EXAMPLE.parse().unwrap();
 
         if let Some((a, b)) = system.connect_into_single_circuit() {
             let result = a.x * b.x;
@@ -1,79 +1,65 @@
+use crate::error::{Error, Result};
+use crate::geometry::{Point, Polygon, Rect};
+use crate::solution::{Answer, Example, Solution};
 use std::str::FromStr;
 
-pub fn solve_problem_1(main_file: &str) -> std::io::Result<()> {
-    let input = std::fs::read_to_string(main_file)?;
+pub struct Day09;
 
-    let system: FloorPlan = input.parse().map_err(|e| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!("Parse error: {}", e),
-        )
-    })?;
-    let largest_area = system.find_largest_area();
-    println!("problem 1: result = {}", largest_area);
-    Ok(())
-}
+const EXAMPLE: &str = "\
+7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3";
 
-pub fn solve_problem_2(main_file: &str) -> std::io::Result<()> {
-    let input = std::fs::read_to_string(main_file)?;
+impl Solution for Day09 {
+    type Input = FloorPlan;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    let system: FloorPlan = input.parse().map_err(|e| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!("Parse error: {}", e),
-        )
-    })?;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some(Answer::Int(50)),
+        part2: Some(Answer::Int(24)),
+    }];
 
-    let largest_area = system.find_largest_valid_rectangle();
-    println!("problem 2: result = {}", largest_area);
-    Ok(())
-}
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Point {
-    x: i32,
-    y: i32,
-}
+    fn part1(plan: &Self::Input) -> Result<u64> {
+        Ok(plan.find_largest_area())
+    }
 
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Point { x, y }
+    fn part2(plan: &Self::Input) -> Result<u64> {
+        Ok(plan.find_largest_valid_rectangle())
     }
 }
 
-struct FloorPlan {
-    points: Vec<Point>,
+pub struct FloorPlan {
+    polygon: Polygon,
 }
 
 impl FloorPlan {
-    fn find_largest_area(&self) -> u64 {
-        let mut largest_area = 0;
-        for i in 0..self.points.len() {
-            for j in i + 1..self.points.len() {
-                let a = &self.points[i];
-                let b = &self.points[j];
-                let area = ((a.x - b.x).unsigned_abs() as u64 + 1)
-                    * ((a.y - b.y).unsigned_abs() as u64 + 1);
-                if area > largest_area {
-                    largest_area = area;
-                }
-            }
-        }
-        largest_area
+    pub fn points(&self) -> &[Point] {
+        self.polygon.vertices()
     }
 
-    fn find_largest_valid_rectangle(&self) -> u64 {
-        let polygon = &self.points;
-        let mut largest_area = 0;
-
-        for i in 0..self.points.len() {
-            for j in i + 1..self.points.len() {
-                let a = &self.points[i];
-                let b = &self.points[j];
-                let area = ((a.x - b.x).unsigned_abs() as u64 + 1)
-                    * ((a.y - b.y).unsigned_abs() as u64 + 1);
+    /// Whether the point is a tile of the polygon, on its edge or inside it.
+    pub fn contains(&self, point: &Point) -> bool {
+        self.polygon.contains(point)
+    }
 
-                if area > largest_area && Self::is_rectangle_inside_polygon(a, b, polygon) {
+    pub fn find_largest_area(&self) -> u64 {
+        let points = self.points();
+        let mut largest_area = 0;
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                let area = Rect::from_corners(points[i], points[j]).area();
+                if area > largest_area {
                     largest_area = area;
                 }
             }
@@ -81,161 +67,79 @@ impl FloorPlan {
         largest_area
     }
 
-    fn is_rectangle_inside_polygon(a: &Point, b: &Point, polygon: &[Point]) -> bool {
-        let min_x = a.x.min(b.x);
-        let max_x = a.x.max(b.x);
-        let min_y = a.y.min(b.y);
-        let max_y = a.y.max(b.y);
-
-        //   (min_x, max_y) -------- (max_x, max_y)
-        //         |                       |
-        //         |                       |
-        //   (min_x, min_y) -------- (max_x, min_y)
-        let c = Point::new(min_x, max_y);
-        let d = Point::new(max_x, min_y);
-
-        // All 4 corners must be inside or on the polygon -- a and b vertices so by definition on
-        // an edge.
-        if !Self::point_inside_or_on_polygon(&c, polygon) {
-            return false;
-        }
-        if !Self::point_inside_or_on_polygon(&d, polygon) {
-            return false;
-        }
-
-        // No polygon edge can cross through the rectangle's interiora
-        !Self::polygon_edge_crosses_rect_interior(polygon, min_x, max_x, min_y, max_y)
+    pub fn find_largest_valid_rectangle(&self) -> u64 {
+        self.largest_valid_rectangle().map_or(0, |rect| rect.area())
     }
 
-    // Ray casting algorithm for point-in-polygon test adapted for axis-aligned polygons
-    //
-    // For a point we try to find either that:
-    // - the point is exactly on a polygon edge (then it's inside or on)
-    // - or we count how many times a ray to +∞ crosses polygon edges
-    //
-    //                        │             │
-    //           outside      │   inside    │      outside
-    //                        │             │
-    //       • ──────────────►│────────────►│──────────────► (2 crossings = even = outside)
-    //                        │             │
-    //                        │  • ────────►│──────────────► (1 crossing = odd = inside)
-    //                        │             │
-    //                        │             │  • ──────────► (0 crossings = even = outside)
-    //                        │             │
-    //                        └─────────────┘
-    //
-    fn point_inside_or_on_polygon(point: &Point, polygon: &[Point]) -> bool {
-        let n = polygon.len();
-        let (px, py) = (point.x, point.y);
-        let mut inside = false;
-
-        for i in 0..n {
-            let j = (i + 1) % n;
-            let (xi, yi) = (polygon[i].x, polygon[i].y);
-            let (xj, yj) = (polygon[j].x, polygon[j].y);
-
-            if xi == xj {
-                // Vertical edge - check if point is on it
-                if px == xi && py >= yi.min(yj) && py <= yi.max(yj) {
-                    return true;
-                }
-
-                // Ray casting: vertical edge crosses horizontal ray
-                //         y
-                //         ↑
-                //         │
-                //      yi ┼──────────────●───────────  (xi, yi)
-                //         │              │
-                //         │              │
-                //      py ┼───●──────────┼──────────────────► ray to +∞
-                //         │  (px,py)     │─────────────
-                //         │              │ vertical edge
-                //         │              │
-                //      yj ┼──────────────●───────────  (xi, yj)
-                //         │              │
-                //         └──────────────┼─────────────────► x
-                //                       xi
-                if (yi > py) != (yj > py) && px < xi {
-                    inside = !inside;
-                }
-            } else if yi == yj {
-                // Horizontal edge - check if point is on it
-                if py == yi && px >= xi.min(xj) && px <= xi.max(xj) {
-                    return true;
-                }
-            }
-        }
+    /// The largest rectangle with red tiles in opposite corners that is inside the polygon.
+    pub fn largest_valid_rectangle(&self) -> Option<Rect> {
+        self.search_valid_rectangles(|_| {})
+    }
 
-        inside
+    /// Every rectangle `largest_valid_rectangle` passes through on its way to the largest one,
+    /// each beating the one found before it, in search order. Collected for the visualizer, the
+    /// solver doesn't keep them.
+    pub fn valid_rectangle_improvements(&self) -> Vec<Rect> {
+        let mut improvements = Vec::new();
+        self.search_valid_rectangles(|rect| improvements.push(*rect));
+        improvements
     }
 
-    // all corners could be inside, but an edge could still cross the rectangle's interior:
-    //       a●━━━━━━━━━━━━━━●c
-    //        ┃              ┃
-    //        ┃    ┌────┐    ┃  ← edge cuts through interio
-    //        ┃    │    │    ┃
-    //       d●━━━━┘    └━━━━●b
-    fn polygon_edge_crosses_rect_interior(
-        polygon: &[Point],
-        min_x: i32,
-        max_x: i32,
-        min_y: i32,
-        max_y: i32,
-    ) -> bool {
-        let n = polygon.len();
+    // The search behind both of the above, `on_improvement` sees each rectangle that beats the
+    // largest one found so far.
+    fn search_valid_rectangles(&self, mut on_improvement: impl FnMut(&Rect)) -> Option<Rect> {
+        let points = self.points();
+        let mut largest_area = 0;
+        let mut largest = None;
 
-        for i in 0..n {
-            let j = (i + 1) % n;
-            let (x1, y1) = (polygon[i].x, polygon[i].y);
-            let (x2, y2) = (polygon[j].x, polygon[j].y);
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                let rect = Rect::from_corners(points[i], points[j]);
+                let area = rect.area();
 
-            if x1 == x2 {
-                // Vertical edge crosses interior if x is strictly inside
-                if x1 > min_x && x1 < max_x {
-                    let edge_min_y = y1.min(y2);
-                    let edge_max_y = y1.max(y2);
-                    if edge_min_y < max_y && edge_max_y > min_y {
-                        return true;
-                    }
-                }
-            } else if y1 == y2 {
-                // Horizontal edge crosses interior if y is strictly inside
-                if y1 > min_y && y1 < max_y {
-                    let edge_min_x = x1.min(x2);
-                    let edge_max_x = x1.max(x2);
-                    if edge_min_x < max_x && edge_max_x > min_x {
-                        return true;
-                    }
+                if area > largest_area && self.polygon.contains_rect(&rect) {
+                    largest_area = area;
+                    on_improvement(&rect);
+                    largest = Some(rect);
                 }
             }
         }
-
-        false
+        largest
     }
 }
 
 impl FromStr for FloorPlan {
-    type Err = String;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let points: Vec<Point> = s
             .lines()
             .map(|line| {
                 let parts: Vec<i32> = line
                     .split(',')
-                    .map(|p| p.trim().parse::<i32>())
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(|e| format!("Failed to parse number: {}", e))?;
+                    .map(|p| {
+                        let p = p.trim();
+                        p.parse::<i32>().map_err(|e| {
+                            Error::parse_at(s, p, format!("Failed to parse number: {}", e))
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
 
                 if parts.len() != 2 {
-                    return Err(format!("Expected 2 coordinates, got {}", parts.len()));
+                    return Err(Error::parse_at(
+                        s,
+                        line,
+                        format!("Expected 2 coordinates, got {}", parts.len()),
+                    ));
                 }
 
                 Ok(Point::new(parts[0], parts[1]))
             })
-            .collect::<Result<Vec<_>, String>>()?;
+            .collect::<Result<Vec<_>>>()?;
 
-        Ok(FloorPlan { points })
+        Ok(FloorPlan {
+            polygon: Polygon::new(points),
+        })
     }
 }
 
@@ -243,26 +147,19 @@ impl FromStr for FloorPlan {
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = "\
-7,1
-11,1
-11,7
-9,7
-9,5
-2,5
-2,3
-7,3";
-
     #[test]
     fn test_sample_problem_1() {
-        let system: FloorPlan = TEST_INPUT.parse().unwrap();
+        let system: FloorPlan = EXAMPLE.parse().unwrap();
         assert_eq!(system.find_largest_area(), 50);
     }
 
     #[test]
     fn test_sample_problem_2() {
-        let system: FloorPlan = TEST_INPUT.parse().unwrap();
+        let system: FloorPlan = EXAMPLE.parse().unwrap();
         assert_eq!(system.find_largest_valid_rectangle(), 24);
+        assert_eq!(
+            system.valid_rectangle_improvements().last(),
+            system.largest_valid_rectangle().as_ref()
+        );
     }
 }
-
@@ -1,3 +1,5 @@
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Example, Solution, Strategy};
 use bitvec::vec::BitVec;
 use good_lp::*;
 use indexmap::IndexSet;
@@ -6,88 +8,134 @@ use nom::{
     IResult,
     branch::alt,
     character::complete::{char, space1, u32},
+    error::ErrorKind,
     multi::{fold_many1, separated_list0, separated_list1},
     sequence::delimited,
 };
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
 use std::str::FromStr;
 
-// unooptimized dfs implementation for problem 1
-#[allow(dead_code)]
-pub fn solve_problem_1_dfs(main_file: &str) -> std::io::Result<()> {
-    let input = std::fs::read_to_string(main_file)?;
-
-    let data: ProblemData = input.parse().map_err(|e| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!("Parse error: {}", e),
-        )
-    })?;
+pub struct Day10;
+
+const EXAMPLE: &str = "\
+[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+";
+
+impl Solution for Day10 {
+    type Input = ProblemData;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some(Answer::Int(7)),
+        part2: Some(Answer::Int(33)),
+    }];
+
+    // the searches are exhaustive, a few machines of the real input keep them busy for a long time
+    const PART1_STRATEGIES: &'static [Strategy<Self::Input, u32>] = &[
+        Strategy {
+            name: "iterative-deepening",
+            solve: Self::part1,
+        },
+        Strategy {
+            name: "dfs",
+            solve: solve_problem_1_dfs,
+        },
+    ];
+
+    const PART2_STRATEGIES: &'static [Strategy<Self::Input, u32>] = &[
+        Strategy {
+            name: "ilp",
+            solve: Self::part2,
+        },
+        Strategy {
+            name: "bfs",
+            solve: solve_problem_2_bfs,
+        },
+    ];
 
-    let result: u32 = data
-        .lines
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(data: &Self::Input) -> Result<u32> {
+        solve_problem_1_iterative_deepening(data)
+    }
+
+    fn part2(data: &Self::Input) -> Result<u32> {
+        solve_problem_2_linear_programming(data)
+    }
+}
+
+// unooptimized dfs implementation for problem 1
+pub fn solve_problem_1_dfs(data: &ProblemData) -> Result<u32> {
+    data.lines
         .iter()
-        .map(|line| {
+        .enumerate()
+        .map(|(i, line)| {
             line.find_solutions()
                 .iter()
                 .map(|s| s.len() as u32)
                 .min()
-                .unwrap_or(0)
+                .ok_or_else(|| no_light_solution(i))
         })
-        .sum();
-
-    println!("problem 1: result = {}", result);
-    Ok(())
+        .sum()
 }
 
-pub fn solve_problem_1_iterative_deepening(main_file: &str) -> std::io::Result<()> {
-    let input = std::fs::read_to_string(main_file)?;
-
-    let data: ProblemData = input.parse().map_err(|e| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!("Parse error: {}", e),
-        )
-    })?;
-
-    let result: u32 = data
-        .lines
+pub fn solve_problem_1_iterative_deepening(data: &ProblemData) -> Result<u32> {
+    data.lines
         .iter()
-        .map(|line| {
-            let res = line.find_optimal_solution().len();
-            res as u32
+        .enumerate()
+        .map(|(i, line)| {
+            line.find_optimal_solution()
+                .map(|presses| presses.len() as u32)
+                .ok_or_else(|| no_light_solution(i))
         })
-        .sum();
-
-    println!("problem 1: result = {}", result);
-    Ok(())
+        .sum()
 }
 
-pub fn solve_problem_2_linear_programming(main_file: &str) -> std::io::Result<()> {
-    let input = std::fs::read_to_string(main_file)?;
-
-    let data: ProblemData = input.parse().map_err(|e| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!("Parse error: {}", e),
-        )
-    })?;
+fn no_light_solution(line: usize) -> Error {
+    Error::NoSolution(format!(
+        "no button presses light up the indicators on line {}",
+        line + 1
+    ))
+}
 
-    let result: u32 = data
-        .lines
+pub fn solve_problem_2_linear_programming(data: &ProblemData) -> Result<u32> {
+    data.lines
         .iter()
         .enumerate()
         .map(|(i, line)| {
             find_minimum_presses_ilp(&line.joltage_requirements, &line.wiring_schematics)
-                .unwrap_or_else(|| {
-                    eprintln!("Warning: No solution found for line {}", i + 1);
-                    0
+                .ok_or_else(|| {
+                    Error::NoSolution(format!(
+                        "no button presses reach the joltage on line {}",
+                        i + 1
+                    ))
                 })
         })
-        .sum();
+        .sum()
+}
 
-    println!("problem 2: result = {}", result);
-    Ok(())
+pub fn solve_problem_2_bfs(data: &ProblemData) -> Result<u32> {
+    data.lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            find_minimum_presses(&line.joltage_requirements, &line.wiring_schematics).ok_or_else(
+                || {
+                    Error::NoSolution(format!(
+                        "no button presses reach the joltage on line {}",
+                        i + 1
+                    ))
+                },
+            )
+        })
+        .sum()
 }
 
 // dfs expands a problem by searching maximally deeply into the search space
@@ -95,8 +143,7 @@ pub fn solve_problem_2_linear_programming(main_file: &str) -> std::io::Result<()
 // each level before going deeper. Especially useful when one or more exist at a shallow depth.
 
 // bfs implementation for problem 2
-#[allow(dead_code)]
-fn find_minimum_presses(target: &Vec<u32>, wiring_schematics: &[Vec<u32>]) -> Option<u32> {
+pub fn find_minimum_presses(target: &Vec<u32>, wiring_schematics: &[Vec<u32>]) -> Option<u32> {
     // BFS: Find shortest path from [0,0,..] to target
     let mut queue = VecDeque::new();
     let mut visited = HashSet::new();
@@ -117,12 +164,12 @@ fn find_minimum_presses(target: &Vec<u32>, wiring_schematics: &[Vec<u32>]) -> Op
     while let Some((state, presses)) = queue.pop_front() {
         iterations += 1;
         if iterations % 100000 == 0 {
-            eprintln!(
-                "Iterations: {}, Queue size: {}, Visited: {}, Depth: {}",
+            tracing::debug!(
                 iterations,
-                queue.len(),
-                visited.len(),
-                presses
+                queue = queue.len(),
+                visited = visited.len(),
+                depth = presses,
+                "searching for the fewest presses"
             );
         }
         // Try pressing each button
@@ -157,7 +204,7 @@ fn find_minimum_presses(target: &Vec<u32>, wiring_schematics: &[Vec<u32>]) -> Op
 
 // Integer Linear Programming approach using good_lp with microlp solver
 // Much cleaner than float-based linear algebra!
-fn find_minimum_presses_ilp(target: &[u32], wiring_schematics: &[Vec<u32>]) -> Option<u32> {
+pub fn find_minimum_presses_ilp(target: &[u32], wiring_schematics: &[Vec<u32>]) -> Option<u32> {
     use good_lp::{Solution, SolverModel, constraint, microlp, variables};
 
     let num_buttons = wiring_schematics.len();
@@ -206,7 +253,7 @@ fn find_minimum_presses_ilp(target: &[u32], wiring_schematics: &[Vec<u32>]) -> O
             Some(total_presses)
         }
         Err(e) => {
-            eprintln!("❌ ILP solver failed: {:?}", e);
+            tracing::warn!(error = ?e, "the ILP solver failed");
             None
         }
     }
@@ -249,14 +296,24 @@ fn find_minimum_presses_ilp(target: &[u32], wiring_schematics: &[Vec<u32>]) -> O
 //     }
 // }
 
-struct ProblemData {
-    lines: Vec<ProblemLine>,
+#[derive(Debug, PartialEq)]
+pub struct ProblemData {
+    pub lines: Vec<ProblemLine>,
+}
+
+impl fmt::Display for ProblemData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
 }
 
 impl FromStr for ProblemData {
-    type Err = String;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         use nom::character::complete::line_ending;
         use nom::combinator::all_consuming;
 
@@ -264,15 +321,22 @@ impl FromStr for ProblemData {
 
         all_consuming(parser)(s.trim())
             .map(|(_, lines)| ProblemData { lines })
-            .map_err(|e| format!("Parse error: {:?}", e))
+            .map_err(|e| match e {
+                nom::Err::Failure(e) if e.code == ErrorKind::Verify => Error::parse_at(
+                    s,
+                    e.input,
+                    "the button wires a light or joltage counter the machine doesn't have",
+                ),
+                e => Error::from_nom(s, e),
+            })
     }
 }
 
-#[derive(Debug)]
-struct ProblemLine {
-    indicator_lights: BitVec,
-    wiring_schematics: Vec<Vec<u32>>,
-    joltage_requirements: Vec<u32>,
+#[derive(Debug, PartialEq)]
+pub struct ProblemLine {
+    pub indicator_lights: BitVec,
+    pub wiring_schematics: Vec<Vec<u32>>,
+    pub joltage_requirements: Vec<u32>,
 }
 
 impl ProblemLine {
@@ -287,8 +351,8 @@ impl ProblemLine {
             .flat_map(|i| {
                 pos_to_button_idx
                     .get(i)
-                    .unwrap()
-                    .iter()
+                    .into_iter()
+                    .flatten()
                     .filter_map(|button_idx| {
                         if already_pressed.contains(button_idx) {
                             None
@@ -309,19 +373,22 @@ impl ProblemLine {
             .collect()
     }
 
-    fn find_solutions(&self) -> Vec<Vec<u32>> {
+    /// The button combinations the search comes across that turn on the lights, none if there is
+    /// no such combination.
+    pub fn find_solutions(&self) -> Vec<Vec<u32>> {
         let positions_to_toggle = self
             .indicator_lights
             .iter()
             .enumerate()
             .filter_map(
                 |(i, light_on)| {
-                    if !*light_on { Some(i as u32) } else { None }
+                    if *light_on { Some(i as u32) } else { None }
                 },
             )
             .collect::<HashSet<_>>();
+        // the lights are already as they should be, no buttons need pressing
         if positions_to_toggle.is_empty() {
-            return Vec::new();
+            return vec![Vec::new()];
         }
 
         let pos_to_button_idx: HashMap<u32, Vec<u32>> = self
@@ -357,10 +424,7 @@ impl ProblemLine {
         assert!(!positions_to_toggle.is_empty());
 
         if already_pressed.len() as u32 >= 50 {
-            println!(
-                "Aborting deep recursion at buttons pressed: {:?}",
-                already_pressed
-            );
+            tracing::debug!(pressed = ?already_pressed, "deep recursion");
         }
         let candidates =
             self.select_candidates(&positions_to_toggle, &already_pressed, pos_to_button_idx);
@@ -422,7 +486,8 @@ impl ProblemLine {
         res
     }
 
-    fn find_optimal_solution(&self) -> Vec<u32> {
+    /// The fewest buttons that turn on the lights, `None` if no combination does.
+    pub fn find_optimal_solution(&self) -> Option<Vec<u32>> {
         let target = self.indicator_lights.clone();
 
         // Convert buttons to BitVec for XOR
@@ -451,16 +516,32 @@ impl ProblemLine {
 
                 if state == target {
                     // Found minimum solution!
-                    return combo.iter().map(|&i| i as u32).collect();
+                    return Some(combo.iter().map(|&i| i as u32).collect());
                 }
             }
         }
 
-        Vec::new() // No solution found
+        None // No solution found
     }
 }
 
-fn parse_problem_line(input: &str) -> IResult<&str, ProblemLine> {
+/// Prints the line as it appears in the input, e.g. `[.##.] (3) (1,3) {3,5,4,7}`.
+impl fmt::Display for ProblemLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lights: String = self
+            .indicator_lights
+            .iter()
+            .map(|on| if *on { '#' } else { '.' })
+            .collect();
+        write!(f, "[{}]", lights)?;
+        for button in &self.wiring_schematics {
+            write!(f, " ({})", button.iter().join(","))?;
+        }
+        write!(f, " {{{}}}", self.joltage_requirements.iter().join(","))
+    }
+}
+
+pub fn parse_problem_line(input: &str) -> IResult<&str, ProblemLine> {
     // parses [.##.]
     let (input, indicator_lights) = delimited(
         char('['),
@@ -477,10 +558,14 @@ fn parse_problem_line(input: &str) -> IResult<&str, ProblemLine> {
 
     let (input, _) = space1(input)?;
 
-    // parses (3) (1,3) ...
-    let (input, wiring_schematics) = separated_list0(
+    // parses (3) (1,3) ..., keeping where each index is to point at one that is out of range
+    fn index(input: &str) -> IResult<&str, (&str, u32)> {
+        let (rest, i) = u32(input)?;
+        Ok((rest, (input, i)))
+    }
+    let (input, buttons) = separated_list0(
         char(' '),
-        delimited(char('('), separated_list0(char(','), u32), char(')')),
+        delimited(char('('), separated_list0(char(','), index), char(')')),
     )(input)?;
 
     let (input, _) = space1(input)?;
@@ -489,6 +574,23 @@ fn parse_problem_line(input: &str) -> IResult<&str, ProblemLine> {
     let (input, joltage_requirements) =
         delimited(char('{'), separated_list1(char(','), u32), char('}'))(input)?;
 
+    let counters = indicator_lights.len().min(joltage_requirements.len());
+    if let Some(&(at, _)) = buttons
+        .iter()
+        .flatten()
+        .find(|(_, i)| *i as usize >= counters)
+    {
+        // a failure, so that it is reported rather than ending the list of lines
+        return Err(nom::Err::Failure(nom::error::Error::new(
+            at,
+            ErrorKind::Verify,
+        )));
+    }
+    let wiring_schematics = buttons
+        .into_iter()
+        .map(|button| button.into_iter().map(|(_, i)| i).collect())
+        .collect();
+
     Ok((
         input,
         ProblemLine {
@@ -502,60 +604,140 @@ fn parse_problem_line(input: &str) -> IResult<&str, ProblemLine> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+    use proptest::strategy::Strategy;
 
-    const TEST_INPUT: &str = "\
-[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
-[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
-[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+    // the puzzle example followed by a large entry from the real input
+    fn test_input() -> String {
+        EXAMPLE.to_string()
+            + "\
 [###.#...#.] (0,1,4,5,6,8,9) (1,3,4,7,8,9) (1,6,7,8) (0,2,3,5,7,8,9) (6,8,9) (1,3,4) (1,4,5) (1,2,6,8) (4,7,9) (0,2,3,4,5,6,7,9) (0,1,2,4,5,6,7) (4,6) (0,1,2,3,5,6,7,9) {46,102,50,59,84,57,75,80,62,55}
-";
+"
+    }
 
     #[test]
     fn test_sample_problem_1() {
-        let data: ProblemData = TEST_INPUT.parse().unwrap();
-        println!(
-            "solutions: {:?}",
-            data.lines[0].find_solutions().iter().map(|s| s.len()).min()
-        );
-        println!(
-            "solutions: {:?}",
-            data.lines[1].find_solutions().iter().map(|s| s.len()).min()
+        let data: ProblemData = test_input().parse().unwrap();
+        let fewest: Vec<Option<usize>> = data.lines[..3]
+            .iter()
+            .map(|line| line.find_solutions().iter().map(|s| s.len()).min())
+            .collect();
+        assert_eq!(fewest, vec![Some(2), Some(3), Some(2)]);
+    }
+
+    #[test]
+    fn test_large_entry() {
+        let data: ProblemData = test_input().parse().unwrap();
+        let line = &data.lines[3];
+        assert_eq!(line.find_optimal_solution(), Some(vec![1, 6, 11, 12]));
+        let fewest = line.find_solutions().iter().map(|s| s.len()).min();
+        assert_eq!(fewest, Some(4));
+    }
+
+    #[test]
+    fn test_button_out_of_range() {
+        let err = "[.#] (0) (5) {1,1}".parse::<ProblemData>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 1, column 11: the button wires a light or joltage counter the \
+             machine doesn't have"
         );
-        println!(
-            "solutions: {:?}",
-            data.lines[2].find_solutions().iter().map(|s| s.len()).min()
+
+        // two lights but a single joltage counter
+        let err = "[.#] (0,1) {1}".parse::<ProblemData>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 1, column 9: the button wires a light or joltage counter the \
+             machine doesn't have"
         );
-        // TODO assert results
     }
 
     #[test]
-    fn test_large_entry() {
-        let data: ProblemData = TEST_INPUT.parse().unwrap();
-        let res = data.lines[3].find_optimal_solution();
-        println!("solutions: {:?}", res);
-        let res2 = data.lines[3].find_solutions();
-        println!("solutions: {:?}", res2);
-        // TODO assert results
+    fn test_unreachable_lights() {
+        let data: ProblemData = "[.#] (0) {1,1}".parse().unwrap();
+        assert_eq!(data.lines[0].find_optimal_solution(), None);
+        for err in [
+            solve_problem_1_iterative_deepening(&data).unwrap_err(),
+            solve_problem_1_dfs(&data).unwrap_err(),
+        ] {
+            assert_eq!(
+                err.to_string(),
+                "no solution: no button presses light up the indicators on line 1"
+            );
+        }
+
+        let off: ProblemData = "[..] (0) {1,1}".parse().unwrap();
+        assert_eq!(solve_problem_1_iterative_deepening(&off).unwrap(), 0);
+        assert_eq!(solve_problem_1_dfs(&off).unwrap(), 0);
     }
 
     #[test]
     fn test_sample_problem_2() {
-        let data: ProblemData = TEST_INPUT.parse().unwrap();
-        let res: u32 = (0..3)
-            .map(|i| {
-                eprintln!("\n======= Testing line {} =======", i);
-                eprintln!("Target: {:?}", data.lines[i].joltage_requirements);
-
-                let r = find_minimum_presses_ilp(
-                    &data.lines[i].joltage_requirements,
-                    &data.lines[i].wiring_schematics,
-                )
-                .expect("must have solution");
-                println!("Line {}: {} total presses\n", i, r);
-                r
+        let data: ProblemData = test_input().parse().unwrap();
+        let presses: Vec<Option<u32>> = data.lines[..3]
+            .iter()
+            .map(|line| {
+                find_minimum_presses_ilp(&line.joltage_requirements, &line.wiring_schematics)
             })
-            .sum();
-        println!("\n=============================");
-        println!("Total for all lines: {}", res);
+            .collect();
+        assert_eq!(presses, vec![Some(10), Some(12), Some(11)]);
+    }
+
+    #[test]
+    fn test_display_problem_data() {
+        let data: ProblemData = EXAMPLE.parse().unwrap();
+        assert_eq!(data.to_string(), EXAMPLE);
+    }
+
+    fn problem_line() -> impl Strategy<Value = ProblemLine> {
+        (1..10usize).prop_flat_map(|counters| {
+            (
+                prop::collection::vec(any::<bool>(), counters),
+                prop::collection::vec(
+                    prop::collection::btree_set(0..counters as u32, 0..=counters),
+                    1..8,
+                ),
+                prop::collection::vec(0..300u32, counters),
+            )
+                .prop_map(|(lights, buttons, joltages)| ProblemLine {
+                    indicator_lights: lights.into_iter().collect(),
+                    wiring_schematics: buttons
+                        .into_iter()
+                        .map(|b| b.into_iter().collect())
+                        .collect(),
+                    joltage_requirements: joltages,
+                })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_problem_data_round_trip(
+            lines in prop::collection::vec(problem_line(), 1..10),
+            // the lights, the buttons and the joltages may be separated by any number of spaces
+            padding in prop::collection::vec((0..3usize, 0..3usize), 10),
+            trailing_newlines in 0..3usize,
+            crlf: bool,
+        ) {
+            let data = ProblemData { lines };
+
+            let mut printed = data
+                .lines
+                .iter()
+                .zip(padding.iter().cycle())
+                .map(|(line, &(after_lights, before_joltages))| {
+                    line.to_string()
+                        .replacen("] ", &format!("]{}", " ".repeat(1 + after_lights)), 1)
+                        .replacen(" {", &format!("{}{{", " ".repeat(1 + before_joltages)), 1)
+                })
+                .join("\n");
+            printed.push_str(&"\n".repeat(trailing_newlines));
+            if crlf {
+                printed = printed.replace('\n', "\r\n");
+            }
+
+            let parsed: ProblemData = printed.parse().unwrap();
+            prop_assert_eq!(parsed, data);
+        }
     }
 }
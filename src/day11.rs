@@ -10,55 +10,100 @@ use petgraph::{
     graph::{DiGraph, NodeIndex},
 };
 
-use std::{collections::HashMap, collections::hash_map::RandomState, str::FromStr};
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Example, Solution};
+use std::{
+    collections::HashMap, collections::HashSet, collections::hash_map::RandomState, fmt,
+    str::FromStr,
+};
 
-pub fn solve_problem_1(main_file: &str) -> std::io::Result<()> {
-    let input = std::fs::read_to_string(main_file)?;
+pub struct Day11;
 
-    let data: ProblemData = input.parse().map_err(|e| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!("Parse error: {}", e),
-        )
-    })?;
+const EXAMPLE_1: &str = "\
+aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out";
 
-    let result = data.solve_part1();
-    println!("problem 1: result = {}", result);
-    Ok(())
-}
+const EXAMPLE_2: &str = "\
+svr: aaa bbb
+aaa: fft
+fft: ccc
+bbb: tty
+tty: ccc
+ccc: ddd eee
+ddd: hub
+hub: fff
+eee: dac
+dac: fff
+fff: ggg hhh
+ggg: out
+hhh: out";
 
-pub fn solve_problem_2(main_file: &str) -> std::io::Result<()> {
-    let input = std::fs::read_to_string(main_file)?;
+impl Solution for Day11 {
+    type Input = ProblemData;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE_1,
+            part1: Some(Answer::Int(5)),
+            part2: None,
+        },
+        Example {
+            input: EXAMPLE_2,
+            part1: None,
+            part2: Some(Answer::Int(2)),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
 
-    let data: ProblemData = input.parse().map_err(|e| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!("Parse error: {}", e),
-        )
-    })?;
+    fn part1(data: &Self::Input) -> Result<u64> {
+        data.solve_part1()
+    }
 
-    let result = data.solve_part2(&["dac", "fft"]);
-    println!("problem 2: result = {}", result);
-    Ok(())
+    fn part2(data: &Self::Input) -> Result<u64> {
+        data.solve_part2(&["dac", "fft"])
+    }
 }
 
-struct ProblemData {
+#[derive(Debug, PartialEq)]
+pub struct ProblemData {
     data: HashMap<String, Vec<String>>,
 }
 
+/// Prints one line per device, sorted by name so the output doesn't depend on the map's order.
+impl fmt::Display for ProblemData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut devices: Vec<_> = self.data.iter().collect();
+        devices.sort();
+        for (device, outputs) in devices {
+            writeln!(f, "{}: {}", device, outputs.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
 impl ProblemData {
     fn build_graph(&self) -> (DiGraph<String, ()>, HashMap<String, NodeIndex>) {
         let mut graph = DiGraph::new();
         let mut nodes = HashMap::new();
 
-        if !self.data.contains_key("out") {
-            nodes.insert("out".to_string(), graph.add_node("out".to_string()));
-        }
-
-        // Add all nodes
-        for key in self.data.keys() {
-            let idx = graph.add_node(key.clone());
-            nodes.insert(key.clone(), idx);
+        // Add all nodes, devices that are only ever an output (like "out") included
+        for device in self.data.keys().chain(self.data.values().flatten()) {
+            nodes
+                .entry(device.clone())
+                .or_insert_with(|| graph.add_node(device.clone()));
         }
 
         // Add edges based on values pointing to their dependencies
@@ -74,31 +119,44 @@ impl ProblemData {
         (graph, nodes)
     }
 
-    fn solve_part1(&self) -> u64 {
+    pub fn solve_part1(&self) -> Result<u64> {
         let (graph, nodes) = self.build_graph();
 
-        let start = nodes["you"];
-        let end = nodes["out"];
+        let start = *nodes
+            .get("you")
+            .ok_or_else(|| Error::InvalidInput("the device 'you' is missing".to_string()))?;
+        let end = *nodes
+            .get("out")
+            .ok_or_else(|| Error::InvalidInput("the device 'out' is missing".to_string()))?;
 
         // Find all simple paths from "you" to "out"
         let paths: Vec<Vec<NodeIndex>> =
             all_simple_paths::<Vec<NodeIndex>, _, RandomState>(&graph, start, end, 0, None)
                 .collect();
 
-        println!("Found {} paths from 'you' to 'out':", paths.len());
-        for (i, path) in paths.iter().enumerate() {
-            let path_str: Vec<String> = path.iter().map(|&idx| graph[idx].clone()).collect();
-            println!("  Path {}: {}", i + 1, path_str.join(" → "));
+        tracing::debug!("found {} paths from 'you' to 'out'", paths.len());
+        if tracing::enabled!(tracing::Level::TRACE) {
+            for (i, path) in paths.iter().enumerate() {
+                let path_str: Vec<String> = path.iter().map(|&idx| graph[idx].clone()).collect();
+                tracing::trace!("path {}: {}", i + 1, path_str.join(" → "));
+            }
         }
 
-        paths.len() as u64
+        Ok(paths.len() as u64)
     }
 
     /// Count paths from svr to out that visit all required nodes.
-    /// Uses memoization on (node, bitmask). Only correct for DAGs.
-    fn solve_part2(&self, required: &[&str]) -> u64 {
+    /// Uses memoization on (node, bitmask). Only correct for DAGs, a cycle is reported as an error.
+    pub fn solve_part2(&self, required: &[&str]) -> Result<u64> {
+        if !self.data.contains_key("svr") {
+            return Err(Error::InvalidInput(
+                "the device 'svr' is missing".to_string(),
+            ));
+        }
+
         let target_mask = (1u8 << required.len()) - 1;
         let mut memo: HashMap<(&str, u8), u64> = HashMap::new();
+        let mut on_path: HashSet<&str> = HashSet::new();
 
         fn dfs<'a>(
             graph: &'a HashMap<String, Vec<String>>,
@@ -107,43 +165,53 @@ impl ProblemData {
             target_mask: u8,
             required: &[&str],
             memo: &mut HashMap<(&'a str, u8), u64>,
-        ) -> u64 {
+            on_path: &mut HashSet<&'a str>,
+        ) -> Result<u64> {
             if current == "out" {
-                return if mask == target_mask { 1 } else { 0 };
+                return Ok(if mask == target_mask { 1 } else { 0 });
             }
 
             if let Some(&cached) = memo.get(&(current, mask)) {
-                return cached;
+                return Ok(cached);
             }
 
-            let count = graph
-                .get(current)
-                .map(|neighbors| {
-                    neighbors
-                        .iter()
-                        .map(|next| {
-                            let new_mask =
-                                required.iter().enumerate().fold(mask, |m, (i, &req)| {
-                                    if next == req { m | (1 << i) } else { m }
-                                });
-                            dfs(graph, next, new_mask, target_mask, required, memo)
-                        })
-                        .sum()
-                })
-                .expect("missing key in graph");
+            // A device without outputs is a dead end
+            let Some(neighbors) = graph.get(current) else {
+                return Ok(0);
+            };
+            if !on_path.insert(current) {
+                return Err(Error::InvalidInput(format!("cycle through '{}'", current)));
+            }
+
+            let mut count = 0;
+            for next in neighbors {
+                let new_mask = required.iter().enumerate().fold(mask, |m, (i, &req)| {
+                    if next == req { m | (1 << i) } else { m }
+                });
+                count += dfs(graph, next, new_mask, target_mask, required, memo, on_path)?;
+            }
 
+            on_path.remove(current);
             memo.insert((current, mask), count);
-            count
+            Ok(count)
         }
 
-        dfs(&self.data, "svr", 0, target_mask, required, &mut memo)
+        dfs(
+            &self.data,
+            "svr",
+            0,
+            target_mask,
+            required,
+            &mut memo,
+            &mut on_path,
+        )
     }
 }
 
 impl FromStr for ProblemData {
-    type Err = String;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         fn parse_line(input: &str) -> IResult<&str, (&str, Vec<&str>)> {
             separated_pair(
                 take(3usize),                          // key: 3 chars
@@ -155,8 +223,7 @@ impl FromStr for ProblemData {
         let mut map = HashMap::new();
 
         for line in s.lines() {
-            let (_, (key, values)) = parse_line(line)
-                .map_err(|e| format!("Failed to parse line '{}': {:?}", line, e))?;
+            let (_, (key, values)) = parse_line(line).map_err(|e| Error::from_nom(s, e))?;
 
             map.insert(
                 key.to_string(),
@@ -171,43 +238,93 @@ impl FromStr for ProblemData {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const TEST_INPUT: &str = "\
-aaa: you hhh
-you: bbb ccc
-bbb: ddd eee
-ccc: ddd eee fff
-ddd: ggg
-eee: out
-fff: out
-ggg: out
-hhh: ccc fff iii
-iii: out";
-
-    const TEST_INPUT_2: &str = "\
-svr: aaa bbb
-aaa: fft
-fft: ccc
-bbb: tty
-tty: ccc
-ccc: ddd eee
-ddd: hub
-hub: fff
-eee: dac
-dac: fff
-fff: ggg hhh
-ggg: out
-hhh: out";
+    use proptest::prelude::*;
 
     #[test]
     fn test_sample_problem_1() {
-        let data: ProblemData = TEST_INPUT.parse().unwrap();
-        assert_eq!(data.solve_part1(), 5);
+        let data: ProblemData = EXAMPLE_1.parse().unwrap();
+        assert_eq!(data.solve_part1().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_missing_devices() {
+        let data: ProblemData = "you: aaa".parse().unwrap();
+        assert_eq!(
+            data.solve_part1().unwrap_err().to_string(),
+            "invalid input: the device 'out' is missing"
+        );
+
+        let data: ProblemData = "aaa: out".parse().unwrap();
+        assert_eq!(
+            data.solve_part1().unwrap_err().to_string(),
+            "invalid input: the device 'you' is missing"
+        );
     }
 
     #[test]
     fn test_sample_problem_2() {
-        let data: ProblemData = TEST_INPUT_2.parse().unwrap();
-        assert_eq!(data.solve_part2(&["dac", "fft"]), 2);
+        let data: ProblemData = EXAMPLE_2.parse().unwrap();
+        assert_eq!(data.solve_part2(&["dac", "fft"]).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_part2_dead_ends_and_cycles() {
+        // aaa has no outputs of its own
+        let data: ProblemData = "svr: aaa".parse().unwrap();
+        assert_eq!(data.solve_part2(&["dac", "fft"]).unwrap(), 0);
+
+        let data: ProblemData = "you: out\nsvr: svr".parse().unwrap();
+        assert_eq!(
+            data.solve_part2(&["dac", "fft"]).unwrap_err().to_string(),
+            "invalid input: cycle through 'svr'"
+        );
+    }
+
+    #[test]
+    fn test_display_problem_data() {
+        let data: ProblemData = "bbb: out\naaa: you bbb\n".parse().unwrap();
+        assert_eq!(data.to_string(), "aaa: you bbb\nbbb: out\n");
+    }
+
+    proptest! {
+        #[test]
+        fn test_problem_data_round_trip(
+            data in prop::collection::hash_map(
+                "[a-z]{3}",
+                prop::collection::vec("[a-z]{3}", 1..5),
+                1..30,
+            ),
+            // outputs may be separated by any run of spaces and tabs
+            separators in prop::collection::vec("[ \t]{1,3}", 10),
+            shuffle_seed: u64,
+            crlf: bool,
+        ) {
+            use rand::SeedableRng;
+            use rand::seq::SliceRandom;
+
+            let data = ProblemData { data };
+
+            let mut separators = separators.iter().cycle();
+            let mut lines: Vec<String> = data
+                .to_string()
+                .lines()
+                .map(|line| {
+                    line.split(' ')
+                        .enumerate()
+                        .map(|(i, name)| match i {
+                            0 => name.to_string(),
+                            1 => format!(" {}", name),
+                            _ => format!("{}{}", separators.next().unwrap(), name),
+                        })
+                        .collect()
+                })
+                .collect();
+            lines.shuffle(&mut rand::rngs::StdRng::seed_from_u64(shuffle_seed));
+            let newline = if crlf { "\r\n" } else { "\n" };
+            let printed = lines.join(newline) + newline;
+
+            let parsed: ProblemData = printed.parse().unwrap();
+            prop_assert_eq!(parsed, data);
+        }
     }
 }
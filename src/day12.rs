@@ -1,92 +1,175 @@
 use nom::{
+    IResult,
+    branch::alt,
     bytes::complete::tag,
     character::complete::{char, digit1, line_ending, one_of, space1},
-    combinator::{map, map_res},
+    combinator::{eof, map, map_res},
     multi::{many1, separated_list1},
     sequence::{separated_pair, terminated, tuple},
-    IResult,
 };
 use rayon::prelude::*;
+use std::fmt;
 
-pub fn solve_problem_1(main_file: &str) -> std::io::Result<()> {
-    let input = std::fs::read_to_string(main_file)?;
-    let data = parse_input(&input).expect("Failed to parse input");
+use crate::error::{Error, Result};
+use crate::geometry::{Point, Rect};
+use crate::grid::Grid;
+use crate::solution::{Answer, Example, Solution, Strategy};
 
-    let result = data.solve_part1();
-    println!("problem 1: result = {}", result);
-    Ok(())
-}
+pub struct Day12;
 
-pub fn solve_problem_2(main_file: &str) -> std::io::Result<()> {
-    let input = std::fs::read_to_string(main_file)?;
-    let data = parse_input(&input).expect("Failed to parse input");
+const EXAMPLE: &str = r#"0:
+###
+##.
+##.
 
-    let result = data.solve_part2();
-    println!("problem 2: result = {}", result);
-    Ok(())
-}
+1:
+###
+##.
+.##
 
-type Coord = (i32, i32);
-type Shape = Vec<Coord>;
+2:
+.##
+###
+##.
 
-#[derive(Debug, Clone)]
-struct ProblemData {
-    shapes: Vec<Vec<Shape>>, // shapes[shape_idx] = all orientations of that shape
-    regions: Vec<Region>,
-}
+3:
+##.
+###
+##.
+
+4:
+###
+#..
+###
+
+5:
+###
+.#.
+###
+
+4x4: 0 0 0 0 2 0
+12x5: 1 0 1 0 2 2
+12x5: 1 0 1 0 3 2"#;
+
+impl Solution for Day12 {
+    type Input = ProblemData;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some(Answer::Int(2)),
+        part2: None,
+    }];
+
+    const PART1_STRATEGIES: &'static [Strategy<Self::Input, u64>] = &[
+        Strategy {
+            name: "parallel",
+            solve: Self::part1,
+        },
+        Strategy {
+            name: "sequential",
+            solve: |data| Ok(data.solve_part1_sequential()),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(data: &Self::Input) -> Result<u64> {
+        Ok(data.solve_part1())
+    }
 
-#[derive(Debug, Clone)]
-struct Region {
-    width: usize,
-    height: usize,
-    required: Vec<usize>, // required[shape_idx] = count needed
+    fn part2(data: &Self::Input) -> Result<u64> {
+        Ok(data.solve_part2())
+    }
 }
 
-// ============ Rotation/Flip Logic ============
+pub type Shape = Vec<Point>;
 
-fn rotate_90(shape: &Shape) -> Shape {
-    shape.iter().map(|(x, y)| (*y, -*x)).collect()
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProblemData {
+    pub shapes: Vec<Vec<Shape>>, // shapes[shape_idx] = all orientations of that shape
+    pub regions: Vec<Region>,
 }
 
-fn flip_horizontal(shape: &Shape) -> Shape {
-    shape.iter().map(|(x, y)| (-*x, *y)).collect()
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region {
+    pub width: usize,
+    pub height: usize,
+    pub required: Vec<usize>, // required[shape_idx] = count needed
 }
 
+// ============ Rotation/Flip Logic ============
+
+// moves the shape so its bounding box starts at the origin
 fn normalize(shape: &Shape) -> Shape {
-    let min_x = shape.iter().map(|(x, _)| *x).min().unwrap_or(0);
-    let min_y = shape.iter().map(|(_, y)| *y).min().unwrap_or(0);
-    shape.iter().map(|(x, y)| (x - min_x, y - min_y)).collect()
+    let min = Rect::bounding(shape).map_or(Point::default(), |bounds| bounds.min);
+    shape.iter().map(|&p| p - min).collect()
 }
 
-fn all_orientations(shape: &Shape) -> Vec<Shape> {
+pub fn all_orientations(shape: &Shape) -> Vec<Shape> {
     let mut results = Vec::new();
     let mut current = shape.clone();
 
     for _ in 0..4 {
         results.push(normalize(&current));
-        results.push(normalize(&flip_horizontal(&current)));
-        current = rotate_90(&current);
+        let flipped = current.iter().map(|p| p.flip_horizontal()).collect();
+        results.push(normalize(&flipped));
+        current = current.iter().map(|p| p.rotate_left()).collect();
     }
 
-    // Deduplicate
-    results.sort_by(|a, b| {
-        let mut a_vec: Vec<_> = a.iter().collect();
-        let mut b_vec: Vec<_> = b.iter().collect();
-        a_vec.sort();
-        b_vec.sort();
-        a_vec.cmp(&b_vec)
-    });
-    results.dedup_by(|a, b| {
-        let mut a_vec: Vec<_> = a.iter().collect();
-        let mut b_vec: Vec<_> = b.iter().collect();
-        a_vec.sort();
-        b_vec.sort();
-        a_vec == b_vec
-    });
+    // Sort the cells so equal orientations compare equal, then deduplicate. This also makes the
+    // orientations the same whichever orientation of the shape was parsed.
+    for orientation in &mut results {
+        orientation.sort();
+    }
+    results.sort();
+    results.dedup();
 
     results
 }
 
+// ============ Printing ============
+
+/// Prints the input back in the puzzle format. Each shape is printed in its first orientation,
+/// which parses back to the same set of orientations.
+impl fmt::Display for ProblemData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, orientations) in self.shapes.iter().enumerate() {
+            writeln!(f, "{}:", idx)?;
+            let shape = &orientations[0];
+            let width = shape.iter().map(|p| p.x + 1).max().unwrap_or(0);
+            let height = shape.iter().map(|p| p.y + 1).max().unwrap_or(0);
+            for y in 0..height {
+                let row: String = (0..width)
+                    .map(|x| {
+                        if shape.contains(&Point::new(x, y)) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect();
+                writeln!(f, "{}", row)?;
+            }
+            writeln!(f)?;
+        }
+        for region in &self.regions {
+            writeln!(f, "{}", region)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let required: Vec<String> = self.required.iter().map(|c| c.to_string()).collect();
+        write!(f, "{}x{}: {}", self.width, self.height, required.join(" "))
+    }
+}
+
 // ============ Parsing with nom ============
 
 fn parse_number(input: &str) -> IResult<&str, usize> {
@@ -97,8 +180,12 @@ fn parse_shape_header(input: &str) -> IResult<&str, usize> {
     terminated(parse_number, tuple((char(':'), line_ending)))(input)
 }
 
+// the last row of a block has no line ending, the blocks are split on the blank lines
 fn parse_shape_row(input: &str) -> IResult<&str, Vec<bool>> {
-    terminated(many1(map(one_of("#."), |c| c == '#')), line_ending)(input)
+    terminated(
+        many1(map(one_of("#."), |c| c == '#')),
+        alt((line_ending, eof)),
+    )(input)
 }
 
 fn parse_shape(input: &str) -> IResult<&str, (usize, Shape)> {
@@ -109,7 +196,7 @@ fn parse_shape(input: &str) -> IResult<&str, (usize, Shape)> {
     for (y, row) in rows.iter().enumerate() {
         for (x, &filled) in row.iter().enumerate() {
             if filled {
-                shape.push((x as i32, y as i32));
+                shape.push(Point::new(x as i32, y as i32));
             }
         }
     }
@@ -118,8 +205,7 @@ fn parse_shape(input: &str) -> IResult<&str, (usize, Shape)> {
 }
 
 fn parse_region(input: &str) -> IResult<&str, Region> {
-    let (input, (width, height)) =
-        separated_pair(parse_number, char('x'), parse_number)(input)?;
+    let (input, (width, height)) = separated_pair(parse_number, char('x'), parse_number)(input)?;
     let (input, _) = tag(": ")(input)?;
     let (input, required) = separated_list1(space1, parse_number)(input)?;
 
@@ -133,12 +219,27 @@ fn parse_region(input: &str) -> IResult<&str, Region> {
     ))
 }
 
-fn parse_input(input: &str) -> Option<ProblemData> {
-    // Split into blocks by double newline
-    let blocks: Vec<&str> = input.split("\n\n").collect();
+/// Splits the input into blocks at blank lines, whether the lines end in LF or CRLF (`--raw`
+/// leaves CRLF in place). The blocks are slices of `input`, so parse errors still point into it.
+fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let (mut start, mut offset) = (0, 0);
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            blocks.push(&input[start..offset]);
+            start = offset + line.len();
+        }
+        offset += line.len();
+    }
+    blocks.push(&input[start..]);
+    blocks
+}
 
-    // Shapes are blocks that start with a digit followed by ':'
-    // Regions are blocks that start with digits followed by 'x'
+pub fn parse_input(input: &str) -> Result<ProblemData> {
+    let blocks = blocks(input);
+
+    // Shapes are blocks that start with their index followed by ':'
+    // Regions are blocks of lines that start with the region size, e.g. "12x5:"
     let mut base_shapes: Vec<Shape> = Vec::new();
     let mut regions: Vec<Region> = Vec::new();
 
@@ -148,73 +249,107 @@ fn parse_input(input: &str) -> Option<ProblemData> {
             continue;
         }
 
-        // Check if this looks like a shape (starts with "N:")
-        if block.chars().next().map(|c| c.is_ascii_digit()).unwrap_or(false)
-            && block.contains(':')
-            && block.contains('#')
-        {
-            // Parse as shape
-            let input_with_newline = format!("{}\n", block);
-            if let Ok((_, (idx, shape))) = parse_shape(&input_with_newline) {
-                // Ensure we have space for this index
-                while base_shapes.len() <= idx {
-                    base_shapes.push(Vec::new());
-                }
-                base_shapes[idx] = shape;
+        let is_region_block = block.lines().next().is_some_and(|line| line.contains('x'));
+        if !is_region_block {
+            let (rest, (idx, shape)) = parse_shape(block).map_err(|e| Error::from_nom(input, e))?;
+            if !rest.is_empty() {
+                return Err(Error::parse_at(input, rest, "unexpected input in shape"));
             }
-        } else if block.contains('x') && block.contains(':') {
-            // Parse as region(s) - might be multiple lines
+            // the shapes are listed in order, the index is where they're looked up by the regions
+            if idx != base_shapes.len() {
+                return Err(Error::parse_at(
+                    input,
+                    block,
+                    format!("expected shape {}, found shape {}", base_shapes.len(), idx),
+                ));
+            }
+            base_shapes.push(shape);
+        } else {
             for line in block.lines() {
                 let line = line.trim();
                 if line.is_empty() {
                     continue;
                 }
-                if let Ok((_, region)) = parse_region(line) {
-                    regions.push(region);
+                match parse_region(line) {
+                    Ok(("", region)) => regions.push(region),
+                    Ok((rest, _)) => {
+                        return Err(Error::parse_at(input, rest, "unexpected input in region"));
+                    }
+                    Err(e) => return Err(Error::from_nom(input, e)),
                 }
             }
         }
     }
 
-    if base_shapes.is_empty() || regions.is_empty() {
-        return None;
+    if base_shapes.is_empty() {
+        return Err(Error::InvalidInput("no shapes found".to_string()));
+    }
+    if regions.is_empty() {
+        return Err(Error::InvalidInput("no regions found".to_string()));
+    }
+    if let Some(region) = regions
+        .iter()
+        .find(|r| r.required.len() != base_shapes.len())
+    {
+        return Err(Error::InvalidInput(format!(
+            "region {} needs a count for each of the {} shapes",
+            region,
+            base_shapes.len()
+        )));
     }
 
     // Precompute all orientations for each shape
-    let shapes: Vec<Vec<Shape>> = base_shapes.iter().map(|s| all_orientations(s)).collect();
+    let shapes: Vec<Vec<Shape>> = base_shapes.iter().map(all_orientations).collect();
 
-    Some(ProblemData { shapes, regions })
+    Ok(ProblemData { shapes, regions })
 }
 
 // ============ Solver ============
 
+/// A step of the search, recorded when the solver is traced. Cells are positions in the region.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    Place { shape: usize, cells: Vec<Point> },
+    Remove { cells: Vec<Point> },
+}
+
+struct Trace {
+    steps: Vec<Step>,
+    limit: usize,
+}
+
 struct Solver {
-    width: i32,
-    height: i32,
-    grid: Vec<bool>,
+    grid: Grid<bool>,
     shapes: Vec<Vec<Shape>>,
+    trace: Option<Trace>,
 }
 
 impl Solver {
     fn new(width: usize, height: usize, shapes: &[Vec<Shape>]) -> Self {
         Self {
-            width: width as i32,
-            height: height as i32,
-            grid: vec![false; width * height],
+            grid: Grid::new(width, height, false),
             shapes: shapes.to_vec(),
+            trace: None,
         }
     }
 
+    fn record(&mut self, step: impl FnOnce() -> Step) {
+        if let Some(trace) = &mut self.trace {
+            trace.steps.push(step());
+        }
+    }
+
+    fn trace_is_full(&self) -> bool {
+        self.trace
+            .as_ref()
+            .is_some_and(|trace| trace.steps.len() >= trace.limit)
+    }
+
     #[inline(always)]
     fn can_place(&self, shape: &Shape, ox: i32, oy: i32) -> bool {
-        for &(sx, sy) in shape {
-            let x = ox + sx;
-            let y = oy + sy;
-            if x < 0 || y < 0 || x >= self.width || y >= self.height {
-                return false;
-            }
-            let idx = (y * self.width + x) as usize;
-            if self.grid[idx] {
+        for p in shape {
+            let (row, col) = (oy + p.y, ox + p.x);
+            if row < 0 || col < 0 || self.grid.get(row as usize, col as usize) != Some(&false) {
                 return false;
             }
         }
@@ -223,22 +358,20 @@ impl Solver {
 
     #[inline(always)]
     fn place(&mut self, shape: &Shape, ox: i32, oy: i32) {
-        for &(sx, sy) in shape {
-            let idx = ((oy + sy) * self.width + (ox + sx)) as usize;
-            self.grid[idx] = true;
+        for p in shape {
+            self.grid[((oy + p.y) as usize, (ox + p.x) as usize)] = true;
         }
     }
 
     #[inline(always)]
     fn remove(&mut self, shape: &Shape, ox: i32, oy: i32) {
-        for &(sx, sy) in shape {
-            let idx = ((oy + sy) * self.width + (ox + sx)) as usize;
-            self.grid[idx] = false;
+        for p in shape {
+            self.grid[((oy + p.y) as usize, (ox + p.x) as usize)] = false;
         }
     }
 
     fn empty_cells(&self) -> usize {
-        self.grid.iter().filter(|&&x| !x).count()
+        self.grid.iter().filter(|(_, filled)| !**filled).count()
     }
 
     /// Count placements for a shape - inlined and optimized
@@ -246,8 +379,8 @@ impl Solver {
     fn count_placements(&self, shape_idx: usize, max_count: usize) -> usize {
         let mut count = 0;
         for orientation in &self.shapes[shape_idx] {
-            for oy in 0..self.height {
-                for ox in 0..self.width {
+            for oy in 0..self.grid.height() as i32 {
+                for ox in 0..self.grid.width() as i32 {
                     if self.can_place(orientation, ox, oy) {
                         count += 1;
                         if count > max_count {
@@ -268,6 +401,11 @@ impl Solver {
             return true;
         }
 
+        // A traced search gives up once it has recorded enough steps
+        if self.trace_is_full() {
+            return false;
+        }
+
         // Early pruning: check if we have enough space
         let empty = self.empty_cells();
         let cells_needed: usize = to_place
@@ -283,12 +421,11 @@ impl Solver {
         let mut best_idx = None;
         let mut min_placements = usize::MAX;
 
-        for idx in 0..to_place.len() {
-            if to_place[idx].1 == 0 {
+        for (idx, &(shape_idx, remaining)) in to_place.iter().enumerate() {
+            if remaining == 0 {
                 continue;
             }
 
-            let shape_idx = to_place[idx].0;
             let placement_count = self.count_placements(shape_idx, min_placements);
 
             // Early exit: if a shape has no valid placements, fail immediately
@@ -317,10 +454,14 @@ impl Solver {
 
         // Try each orientation at each position
         for orientation in &orientations {
-            for oy in 0..self.height {
-                for ox in 0..self.width {
+            for oy in 0..self.grid.height() as i32 {
+                for ox in 0..self.grid.width() as i32 {
                     if self.can_place(orientation, ox, oy) {
                         self.place(orientation, ox, oy);
+                        self.record(|| Step::Place {
+                            shape: shape_idx,
+                            cells: offset(orientation, ox, oy),
+                        });
                         to_place[idx].1 -= 1;
 
                         if self.solve(to_place) {
@@ -329,6 +470,9 @@ impl Solver {
 
                         to_place[idx].1 += 1;
                         self.remove(orientation, ox, oy);
+                        self.record(|| Step::Remove {
+                            cells: offset(orientation, ox, oy),
+                        });
                     }
                 }
             }
@@ -338,42 +482,77 @@ impl Solver {
     }
 }
 
-fn can_fit_region(region: &Region, shapes: &[Vec<Shape>]) -> bool {
-    let mut solver = Solver::new(region.width, region.height, shapes);
+fn offset(shape: &Shape, ox: i32, oy: i32) -> Vec<Point> {
+    shape.iter().map(|&p| p + Point::new(ox, oy)).collect()
+}
 
-    // Build to_place list
-    let mut to_place: Vec<(usize, usize)> = region
+// (shape_idx, count) for every shape the region needs
+fn pieces_to_place(region: &Region) -> Vec<(usize, usize)> {
+    region
         .required
         .iter()
         .enumerate()
         .map(|(idx, &count)| (idx, count))
-        .collect();
+        .collect()
+}
+
+pub fn can_fit_region(region: &Region, shapes: &[Vec<Shape>]) -> bool {
+    let mut solver = Solver::new(region.width, region.height, shapes);
+    solver.solve(&mut pieces_to_place(region))
+}
+
+/// Runs the search for a region while recording its steps, giving up after `limit` steps.
+/// Returns the steps and whether the region fits, `None` if the search gave up.
+pub fn trace_region(
+    region: &Region,
+    shapes: &[Vec<Shape>],
+    limit: usize,
+) -> (Vec<Step>, Option<bool>) {
+    let mut solver = Solver::new(region.width, region.height, shapes);
+    solver.trace = Some(Trace {
+        steps: Vec::new(),
+        limit,
+    });
 
-    solver.solve(&mut to_place)
+    let fits = solver.solve(&mut pieces_to_place(region));
+    let full = solver.trace_is_full();
+    let steps = solver.trace.map(|trace| trace.steps).unwrap_or_default();
+    (steps, if fits || !full { Some(fits) } else { None })
 }
 
 impl ProblemData {
-    fn solve_part1(&self) -> u64 {
+    pub fn solve_part1(&self) -> u64 {
         use std::sync::atomic::{AtomicU64, Ordering};
 
         let count = AtomicU64::new(0);
         let prog = AtomicU64::new(0);
         let total = self.regions.len();
 
+        // the regions are packed on the rayon threads, which don't know the caller's span
+        let span = tracing::Span::current();
         self.regions.par_iter().for_each(|region| {
+            let _span = span.enter();
             if can_fit_region(region, &self.shapes) {
                 count.fetch_add(1, Ordering::Relaxed);
             }
-            let idx = prog.fetch_add(1, Ordering::Relaxed);
-            if  idx % 50 == 0 {
-                eprintln!("Processed {}/{} regions...", idx, total);
+            let done = prog.fetch_add(1, Ordering::Relaxed) + 1;
+            if done.is_multiple_of(50) {
+                tracing::info!("packed {}/{} regions", done, total);
             }
         });
 
         count.load(Ordering::Relaxed)
     }
 
-    fn solve_part2(&self) -> u64 {
+    /// `solve_part1` on a single thread, to see what rayon buys.
+    pub fn solve_part1_sequential(&self) -> u64 {
+        self.regions
+            .iter()
+            .filter(|region| can_fit_region(region, &self.shapes))
+            .count() as u64
+    }
+
+    pub fn solve_part2(&self) -> u64 {
         // Part 2 not yet defined
         0
     }
@@ -382,57 +561,37 @@ impl ProblemData {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const TEST_INPUT: &str = r#"0:
-###
-##.
-##.
-
-1:
-###
-##.
-.##
-
-2:
-.##
-###
-##.
-
-3:
-##.
-###
-##.
-
-4:
-###
-#..
-###
-
-5:
-###
-.#.
-###
-
-4x4: 0 0 0 0 2 0
-12x5: 1 0 1 0 2 2
-12x5: 1 0 1 0 3 2"#;
+    use proptest::prelude::*;
+    use proptest::strategy::Strategy;
 
     #[test]
     fn test_sample_problem_1() {
-        let data = parse_input(TEST_INPUT).unwrap();
-        eprintln!("Parsed {} shapes, {} regions", data.shapes.len(), data.regions.len());
+        let data = parse_input(EXAMPLE).unwrap();
+        eprintln!(
+            "Parsed {} shapes, {} regions",
+            data.shapes.len(),
+            data.regions.len()
+        );
         for (i, shape_orientations) in data.shapes.iter().enumerate() {
-            eprintln!("Shape {}: {} cells, {} orientations", i, shape_orientations[0].len(), shape_orientations.len());
+            eprintln!(
+                "Shape {}: {} cells, {} orientations",
+                i,
+                shape_orientations[0].len(),
+                shape_orientations.len()
+            );
         }
         for (i, region) in data.regions.iter().enumerate() {
-            eprintln!("Region {}: {}x{}, required: {:?}", i, region.width, region.height, region.required);
+            eprintln!(
+                "Region {}: {}x{}, required: {:?}",
+                i, region.width, region.height, region.required
+            );
         }
         assert_eq!(data.solve_part1(), 2);
     }
 
     #[test]
     fn test_rotations() {
-        let shape = vec![(0, 0), (1, 0), (0, 1)];
+        let shape = vec![Point::new(0, 0), Point::new(1, 0), Point::new(0, 1)];
 
         let orientations = all_orientations(&shape);
         // L-shape should have 4 unique orientations
@@ -469,11 +628,11 @@ mod tests {
         eprintln!("\nShape 0 orientations:");
         for (i, orientation) in data.shapes[0].iter().enumerate() {
             eprintln!("Orientation {}:", i);
-            let max_x = orientation.iter().map(|(x, _)| *x).max().unwrap_or(0);
-            let max_y = orientation.iter().map(|(_, y)| *y).max().unwrap_or(0);
+            let max_x = orientation.iter().map(|p| p.x).max().unwrap_or(0);
+            let max_y = orientation.iter().map(|p| p.y).max().unwrap_or(0);
             for y in 0..=max_y {
                 for x in 0..=max_x {
-                    if orientation.contains(&(x, y)) {
+                    if orientation.contains(&Point::new(x, y)) {
                         eprint!("#");
                     } else {
                         eprint!(".");
@@ -486,4 +645,84 @@ mod tests {
 
         assert!(can_fit_region(&data.regions[0], &data.shapes));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_malformed_input() {
+        // a shape index far out of order used to allocate room for every shape before it
+        assert!(parse_input("0:\n#\n\n99999999:\n#\n\n1x1: 1 1").is_err());
+        // blocks that don't parse are reported rather than skipped
+        assert!(parse_input("0:\n#?\n\n1x1: 1").is_err());
+        assert!(parse_input("0:\n#\n\n1x1: 1\n2y2: 1").is_err());
+        // every region needs a count for each shape
+        assert!(parse_input("0:\n#\n\n1:\n##\n\n1x1: 1").is_err());
+    }
+
+    #[test]
+    fn test_display_problem_data() {
+        let data = parse_input("0:\n.#.\n###\n\n4x4: 2\n").unwrap();
+        // the shape comes out in its first orientation, not the one it was read in
+        assert_eq!(data.to_string(), "0:\n#.\n##\n#.\n\n4x4: 2\n");
+    }
+
+    // any non-empty shape in a 3x3 box, listed row by row like the parser does
+    fn shape() -> impl Strategy<Value = Shape> {
+        prop::collection::vec(any::<bool>(), 9)
+            .prop_filter("at least one cell", |cells| cells.contains(&true))
+            .prop_map(|cells| {
+                (0..9)
+                    .filter(|&i| cells[i])
+                    .map(|i| Point::new((i % 3) as i32, (i / 3) as i32))
+                    .collect()
+            })
+    }
+
+    fn problem_data() -> impl Strategy<Value = ProblemData> {
+        prop::collection::vec(shape(), 1..7).prop_flat_map(|shapes| {
+            let count = shapes.len();
+            let region = (
+                1..60usize,
+                1..60usize,
+                prop::collection::vec(0..40usize, count),
+            )
+                .prop_map(|(width, height, required)| Region {
+                    width,
+                    height,
+                    required,
+                });
+            prop::collection::vec(region, 1..20).prop_map(move |regions| ProblemData {
+                shapes: shapes.iter().map(all_orientations).collect(),
+                regions,
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_problem_data_round_trip(
+            data in problem_data(),
+            // blocks may be separated by more than one blank line, and the counts by more than
+            // one space
+            extra_blank_lines in 0..3usize,
+            extra_spaces in 0..3usize,
+            crlf: bool,
+        ) {
+            let mut printed = data
+                .to_string()
+                .replace("\n\n", &"\n".repeat(2 + extra_blank_lines))
+                .lines()
+                .map(|line| match line.split_once(": ") {
+                    Some((size, counts)) => {
+                        format!("{}: {}", size, counts.replace(' ', &" ".repeat(1 + extra_spaces)))
+                    }
+                    None => line.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            if crlf {
+                printed = printed.replace('\n', "\r\n");
+            }
+
+            prop_assert_eq!(parse_input(&printed).unwrap(), data);
+        }
+    }
+}
@@ -1,45 +1,60 @@
+use crate::error::{Error, Result};
 use nom::{
     IResult,
     branch::alt,
     character::complete::{char, digit1, line_ending, space0, space1},
-    combinator::{map, map_res},
+    combinator::{consumed, map, map_res},
+    error::ErrorKind,
     multi::separated_list1,
-    sequence::terminated,
+    sequence::{delimited, terminated},
 };
-use std::fs;
+use std::fmt;
 
-pub fn solve_problem_1(main_file: &str) -> std::io::Result<()> {
-    let problems = read_worksheet_file(main_file)?;
-    let solution: u64 = problems.iter().map(|p| p.solve()).sum();
-    println!("problem 1: solution: {}", solution);
-    Ok(())
+pub fn solve_problem_1(problems: &[Problem]) -> u64 {
+    problems.iter().map(|p| p.solve()).sum()
 }
 
-fn read_worksheet_file(file: &str) -> std::io::Result<Vec<Problem>> {
-    let content = fs::read_to_string(file)?;
-    let (_, problems) = parse_problems(&content)
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+pub fn read_worksheet(content: &str) -> Result<Vec<Problem>> {
+    let (_, problems) = parse_problems(content).map_err(|e| match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) if e.code == ErrorKind::Verify => {
+            Error::parse_at(
+                content,
+                e.input,
+                "the row has a different number of columns",
+            )
+        }
+        e => Error::from_nom(content, e),
+    })?;
     Ok(problems)
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Op {
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
     Add,
     Mul,
 }
 
-#[derive(Debug)]
-struct Problem {
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::Add => f.pad("+"),
+            Op::Mul => f.pad("*"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Problem {
     operands: Vec<u64>,
     op: Op,
 }
 
 impl Problem {
-    fn new(operands: Vec<u64>, op: Op) -> Self {
+    pub fn new(operands: Vec<u64>, op: Op) -> Self {
         Self { operands, op }
     }
 
-    fn solve(&self) -> u64 {
+    pub fn solve(&self) -> u64 {
         match self.op {
             Op::Add => self.operands.iter().sum(),
             Op::Mul => self.operands.iter().product(),
@@ -47,7 +62,43 @@ impl Problem {
     }
 }
 
-fn parse_problems(input: &str) -> IResult<&str, Vec<Problem>> {
+/// Writes the problems back as a worksheet, one problem per column. Numbers are right aligned
+/// within their column and the operator sits under the column. Every problem must have the same
+/// number of operands, like the rows of the worksheet.
+pub fn format_worksheet(problems: &[Problem]) -> String {
+    let widths: Vec<usize> = problems
+        .iter()
+        .map(|p| {
+            p.operands
+                .iter()
+                .map(|n| n.to_string().len())
+                .max()
+                .unwrap_or(1)
+        })
+        .collect();
+    let rows = problems.first().map(|p| p.operands.len()).unwrap_or(0);
+
+    let mut out = String::new();
+    for row in 0..rows {
+        let cells: Vec<String> = problems
+            .iter()
+            .zip(&widths)
+            .map(|(p, &width)| format!("{:>width$}", p.operands[row]))
+            .collect();
+        out.push_str(&cells.join(" "));
+        out.push('\n');
+    }
+    let ops: Vec<String> = problems
+        .iter()
+        .zip(&widths)
+        .map(|(p, &width)| format!("{:<width$}", p.op))
+        .collect();
+    out.push_str(&ops.join(" "));
+    out.push('\n');
+    out
+}
+
+pub fn parse_problems(input: &str) -> IResult<&str, Vec<Problem>> {
     fn parse_u64(input: &str) -> IResult<&str, u64> {
         map_res(digit1, str::parse)(input)
     }
@@ -56,18 +107,36 @@ fn parse_problems(input: &str) -> IResult<&str, Vec<Problem>> {
         alt((map(char('+'), |_| Op::Add), map(char('*'), |_| Op::Mul)))(input)
     }
 
-    // Parse rows of numbers
+    // Parse rows of numbers, numbers are aligned within their column so rows may start with spaces
     let (input, rows) = separated_list1(
         line_ending,
-        terminated(separated_list1(space1, parse_u64), space0),
+        consumed(delimited(
+            space0,
+            separated_list1(space1, parse_u64),
+            space0,
+        )),
     )(input)?;
     let (input, _) = line_ending(input)?;
 
     // Parse operators separated by spaces, with optional trailing spaces
-    let (input, ops) = terminated(separated_list1(space1, parse_op), space0)(input)?;
+    let (input, (ops_row, ops)) =
+        consumed(terminated(separated_list1(space1, parse_op), space0))(input)?;
+
+    // Every row needs a number, and the last one an operator, for each column
+    let num_cols = rows[0].1.len();
+    let lengths = rows.iter().map(|(row, numbers)| (*row, numbers.len()));
+    if let Some((row, _)) = lengths
+        .chain([(ops_row, ops.len())])
+        .find(|(_, len)| *len != num_cols)
+    {
+        return Err(nom::Err::Failure(nom::error::Error::new(
+            row,
+            ErrorKind::Verify,
+        )));
+    }
+    let rows: Vec<Vec<u64>> = rows.into_iter().map(|(_, numbers)| numbers).collect();
 
     // Transpose: each column becomes a problem with operands from each row
-    let num_cols = rows.first().map(|r| r.len()).unwrap_or(0);
     let problems = (0..num_cols)
         .map(|col_idx| {
             let operands: Vec<u64> = rows.iter().map(|row| row[col_idx]).collect();
@@ -82,6 +151,7 @@ fn parse_problems(input: &str) -> IResult<&str, Vec<Problem>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     fn test_data() -> &'static str {
         "123 328  51 64 \n45 64  387 23 \n6 98  215 314 \n*   +   *   +"
@@ -107,4 +177,66 @@ mod tests {
         assert_eq!(problems[3].operands, vec![64, 23, 314]);
         assert!(matches!(problems[3].op, Op::Add));
     }
+
+    #[test]
+    fn test_read_ragged_worksheet() {
+        let err = read_worksheet("1 2\n3\n+ *").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 1: the row has a different number of columns"
+        );
+        assert!(read_worksheet("1 2\n3 4\n+").is_err());
+    }
+
+    #[test]
+    fn test_format_worksheet() {
+        let (_, problems) = parse_problems(test_data()).unwrap();
+        assert_eq!(
+            format_worksheet(&problems),
+            "123 328  51  64\n 45  64 387  23\n  6  98 215 314\n*   +   *   +  \n"
+        );
+    }
+
+    fn problems() -> impl Strategy<Value = Vec<Problem>> {
+        (1..5usize).prop_flat_map(|rows| {
+            prop::collection::vec(
+                (
+                    prop::collection::vec(0u64..100_000, rows),
+                    prop_oneof![Just(Op::Add), Just(Op::Mul)],
+                ),
+                1..10,
+            )
+            .prop_map(|columns| {
+                columns
+                    .into_iter()
+                    .map(|(operands, op)| Problem::new(operands, op))
+                    .collect()
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_worksheet_round_trip(
+            problems in problems(),
+            // extra spaces for every space in the worksheet, numbers only need to be separated
+            padding in prop::collection::vec(0..3usize, 200),
+            crlf: bool,
+        ) {
+            let mut padding = padding.into_iter().cycle();
+            let mut printed: String = format_worksheet(&problems)
+                .chars()
+                .map(|c| match c {
+                    ' ' => " ".repeat(1 + padding.next().unwrap()),
+                    c => c.to_string(),
+                })
+                .collect();
+            if crlf {
+                printed = printed.replace('\n', "\r\n");
+            }
+
+            let (_, parsed) = parse_problems(&printed).unwrap();
+            prop_assert_eq!(parsed, problems);
+        }
+    }
 }
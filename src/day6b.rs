@@ -1,46 +1,40 @@
-use std::fs;
-
-pub fn solve_problem_2(file_path: &str) -> std::io::Result<()> {
-    let content = fs::read_to_string(file_path)?;
-    let reader = WorksheetReader::new(&content).unwrap();
+use crate::error::{Error, Result};
 
+pub fn solve_problem_2(columns: &[(u64, Option<Op>)]) -> u64 {
     let mut tally = 0_u64;
     let mut operands: Vec<u64> = Vec::new();
 
-    for it in reader {
-        match it {
-            Ok((operand, op)) => {
-                operands.push(operand);
-                match op {
-                    Some(Op::Add) => {
-                        let sum: u64 = operands.iter().sum();
-                        tally += sum;
-                        operands.clear();
-                    }
-                    Some(Op::Mull) => {
-                        let product: u64 = operands.iter().product();
-                        tally += product;
-                        operands.clear();
-                    }
-                    None => {}
-                }
+    for (operand, op) in columns {
+        operands.push(*operand);
+        match op {
+            Some(Op::Add) => {
+                let sum: u64 = operands.iter().sum();
+                tally += sum;
+                operands.clear();
             }
-            Err(e) => {
-                return Err(e);
+            Some(Op::Mull) => {
+                let product: u64 = operands.iter().product();
+                tally += product;
+                operands.clear();
             }
+            None => {}
         }
     }
-    println!("problem 2: the total of the worksheet is {}", tally);
-    Ok(())
+    tally
+}
+
+/// Reads the worksheet right to left, one column at a time.
+pub fn read_columns(content: &str) -> Result<Vec<(u64, Option<Op>)>> {
+    WorksheetReader::new(content)?.collect()
 }
 
 #[derive(Debug, PartialEq)]
-enum Op {
+pub enum Op {
     Add,
     Mull,
 }
 
-struct WorksheetReader {
+pub struct WorksheetReader {
     operand_rows: Vec<String>,
     operator_row: String,
     pos: usize,
@@ -49,18 +43,35 @@ struct WorksheetReader {
 }
 
 impl WorksheetReader {
-    fn new(content: &str) -> Result<WorksheetReader, &str> {
+    pub fn new(content: &str) -> Result<WorksheetReader> {
         let mut operator_rows: Vec<String> = content.lines().map(|l| l.to_string()).collect();
         if operator_rows.len() < 2 {
-            return Err("Worksheet must have 2 lines minimum");
+            return Err(Error::InvalidInput(
+                "Worksheet must have 2 lines minimum".to_string(),
+            ));
         }
 
         let len = operator_rows.first().unwrap().len();
-        operator_rows.iter().try_for_each(|row| {
-            if row.len() != len {
-                Err("Inconsistent row lengths in worksheet")
-            } else if !row.is_ascii() {
-                Err("input is expected to be ascii")
+        if len == 0 {
+            return Err(Error::parse(
+                1,
+                1,
+                "the first row of the worksheet is empty",
+            ));
+        }
+        operator_rows.iter().enumerate().try_for_each(|(i, row)| {
+            if let Some(col) = row.chars().position(|c| !c.is_ascii()) {
+                Err(Error::parse(
+                    i + 1,
+                    col + 1,
+                    "input is expected to be ascii",
+                ))
+            } else if row.len() != len {
+                Err(Error::parse(
+                    i + 1,
+                    row.len().min(len) + 1,
+                    "Inconsistent row lengths in worksheet",
+                ))
             } else {
                 Ok(())
             }
@@ -79,26 +90,36 @@ impl WorksheetReader {
 }
 
 impl Iterator for WorksheetReader {
-    type Item = std::io::Result<(u64, Option<Op>)>;
+    type Item = Result<(u64, Option<Op>)>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.finished {
             None
         } else {
             self.scratch.clear();
-            for row in self.operand_rows.iter() {
+            for (i, row) in self.operand_rows.iter().enumerate() {
                 let c = row.as_bytes()[self.pos] as char;
                 if c == ' ' {
                 } else if c.is_ascii_digit() {
                     self.scratch.push(c);
                 } else {
-                    return Some(Err(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
+                    return Some(Err(Error::parse(
+                        i + 1,
+                        self.pos + 1,
                         format!("Invalid character '{}' in operand rows", c),
                     )));
                 }
             }
-            let operand: u64 = self.scratch.parse().unwrap();
+            let operand: u64 = match self.scratch.parse() {
+                Ok(operand) => operand,
+                Err(e) => {
+                    return Some(Err(Error::parse(
+                        1,
+                        self.pos + 1,
+                        format!("Invalid number in column: {}", e),
+                    )));
+                }
+            };
 
             let c = self.operator_row.as_bytes()[self.pos] as char;
             let op = if c == ' ' {
@@ -108,8 +129,9 @@ impl Iterator for WorksheetReader {
             } else if c == '*' {
                 Some(Op::Mull)
             } else {
-                return Some(Err(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
+                return Some(Err(Error::parse(
+                    self.operand_rows.len() + 1,
+                    self.pos + 1,
                     format!("Invalid operator character '{}' in operator row", c),
                 )));
             };
@@ -120,8 +142,21 @@ impl Iterator for WorksheetReader {
                 self.pos -= 1; // we have read the entire column now
             }
 
+            // the operator ends a problem, skip the blank column before the next one
             if op.is_some() && !self.finished {
-                self.pos -= 1;
+                let mut rows = self.operand_rows.iter().chain([&self.operator_row]);
+                if let Some(row) = rows.position(|row| row.as_bytes()[self.pos] != b' ') {
+                    return Some(Err(Error::parse(
+                        row + 1,
+                        self.pos + 1,
+                        "Expected a blank column between problems",
+                    )));
+                }
+                if self.pos == 0 {
+                    self.finished = true;
+                } else {
+                    self.pos -= 1;
+                }
             }
 
             Some(Ok((operand, op)))
@@ -176,4 +211,12 @@ mod tests {
         let (number, _op) = reader.next().unwrap().unwrap();
         assert_eq!(number, 623);
     }
+
+    #[test]
+    fn test_malformed_worksheets() {
+        // a blank column, a missing separator column and no columns at all
+        for input in ["1 2\n+  ", "12\n +", "\n\n"] {
+            assert!(read_columns(input).is_err(), "{:?} should not parse", input);
+        }
+    }
 }
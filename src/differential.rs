@@ -0,0 +1,464 @@
+// Differential testing: runs a fast solver and a slow, obviously correct counterpart against many
+// small random inputs. The first input they disagree on is shrunk (rows, buttons, range ends are
+// dropped one at a time while the disagreement remains) and reported, so a broken optimization
+// comes with a minimal reproduction.
+
+use std::fmt::Debug;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::error::Error;
+use crate::{day02, day07, day10};
+
+pub struct Check {
+    pub name: &'static str,
+    pub description: &'static str,
+    run: fn(&mut StdRng, usize) -> Option<Counterexample>,
+}
+
+/// A (minimized) input the two solvers disagree on, along with what each of them returned.
+#[derive(Debug)]
+pub struct Counterexample {
+    pub input: String,
+    pub fast: String,
+    pub slow: String,
+}
+
+pub const CHECKS: &[Check] = &[
+    Check {
+        name: "day02-twice",
+        description: "is_twice_repeated against comparing the halves of the number as a string",
+        run: day02_twice,
+    },
+    Check {
+        name: "day02-repeating",
+        description: "smallest_repeating_pattern against repeating every prefix of the string",
+        run: day02_repeating,
+    },
+    Check {
+        name: "day07-paths",
+        description: "count_all_paths against enumerating the paths with trace_all_paths",
+        run: day07_paths,
+    },
+    Check {
+        name: "day10-lights",
+        description: "find_solutions (DFS) against find_optimal_solution (all combinations)",
+        run: day10_lights,
+    },
+    Check {
+        name: "day10-joltage",
+        description: "find_minimum_presses_ilp against the find_minimum_presses BFS",
+        run: day10_joltage,
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Check> {
+    CHECKS.iter().find(|c| c.name == name)
+}
+
+/// Runs the named checks, all of them if none are named, and prints their outcome. Returns whether
+/// every check passed.
+pub fn run_checks(names: &[String], cases: usize, seed: u64) -> Result<bool, Error> {
+    let checks = if names.is_empty() {
+        CHECKS.iter().collect()
+    } else {
+        names
+            .iter()
+            .map(|name| {
+                find(name).ok_or_else(|| Error::InvalidInput(format!("unknown check {}", name)))
+            })
+            .collect::<Result<Vec<_>, Error>>()?
+    };
+
+    // a panicking solver is reported as a mismatch, don't print every panic hit while shrinking.
+    // The hook is put back afterwards, so panics elsewhere are reported as usual
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let mut passed = true;
+    for check in checks {
+        match check.run(cases, seed) {
+            None => println!("{}: ok ({} cases)", check.name, cases),
+            Some(counterexample) => {
+                passed = false;
+                println!("{}: MISMATCH, {}", check.name, check.description);
+                println!(
+                    "  input: {}",
+                    counterexample.input.replace('\n', "\n         ")
+                );
+                println!("  fast:  {}", counterexample.fast);
+                println!("  slow:  {}", counterexample.slow);
+            }
+        }
+    }
+
+    std::panic::set_hook(hook);
+    Ok(passed)
+}
+
+impl Check {
+    /// Runs `cases` random inputs, returning the first disagreement after minimizing it.
+    pub fn run(&self, cases: usize, seed: u64) -> Option<Counterexample> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (self.run)(&mut rng, cases)
+    }
+}
+
+/// The harness shared by every check. A solver that panics disagrees with one that doesn't, the
+/// panic message stands in for its answer.
+fn differential<I, T>(
+    rng: &mut StdRng,
+    cases: usize,
+    generate: impl Fn(&mut StdRng) -> I,
+    shrink: impl Fn(&I) -> Vec<I>,
+    render: impl Fn(&I) -> String,
+    fast: impl Fn(&I) -> T,
+    slow: impl Fn(&I) -> T,
+) -> Option<Counterexample>
+where
+    T: PartialEq + Debug,
+{
+    let outcomes = |input: &I| (guarded(|| fast(input)), guarded(|| slow(input)));
+    let disagrees = |input: &I| {
+        let (fast, slow) = outcomes(input);
+        fast != slow
+    };
+
+    let mut input = (0..cases)
+        .map(|_| generate(rng))
+        .find(|input| disagrees(input))?;
+
+    // greedily take the first smaller input that still disagrees until none does
+    while let Some(smaller) = shrink(&input).into_iter().find(|i| disagrees(i)) {
+        input = smaller;
+    }
+
+    let (fast, slow) = outcomes(&input);
+    Some(Counterexample {
+        input: render(&input),
+        fast: describe(fast),
+        slow: describe(slow),
+    })
+}
+
+fn guarded<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    catch_unwind(AssertUnwindSafe(f)).map_err(|panic| {
+        panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+fn describe<T: Debug>(outcome: Result<T, String>) -> String {
+    match outcome {
+        Ok(value) => format!("{:?}", value),
+        Err(message) => format!("panicked: {}", message),
+    }
+}
+
+// day 2: the invalid IDs in a small range
+
+fn id_range(rng: &mut StdRng) -> (u64, u64) {
+    let digits = rng.random_range(1..=8);
+    let low = rng.random_range(10u64.pow(digits - 1)..10u64.pow(digits));
+    (low, low + rng.random_range(0..200))
+}
+
+fn shrink_range(&(low, high): &(u64, u64)) -> Vec<(u64, u64)> {
+    let mid = low + (high - low) / 2;
+    let mut smaller = vec![
+        (low, mid),
+        (mid + 1, high),
+        (low + 1, high),
+        (low, high - 1),
+    ];
+    smaller.retain(|(l, h)| l <= h && (*l, *h) != (low, high));
+    smaller
+}
+
+fn render_range(&(low, high): &(u64, u64)) -> String {
+    format!("{}-{}", low, high)
+}
+
+fn day02_twice(rng: &mut StdRng, cases: usize) -> Option<Counterexample> {
+    differential(
+        rng,
+        cases,
+        id_range,
+        shrink_range,
+        render_range,
+        |&(low, high)| day02::collect_invalid_numbers(low, high, day02::is_twice_repeated),
+        |&(low, high)| {
+            (low..=high)
+                .filter(|n| {
+                    let s = n.to_string();
+                    let (left, right) = s.split_at(s.len() / 2);
+                    s.len() % 2 == 0 && left == right
+                })
+                .collect()
+        },
+    )
+}
+
+fn day02_repeating(rng: &mut StdRng, cases: usize) -> Option<Counterexample> {
+    differential(
+        rng,
+        cases,
+        id_range,
+        shrink_range,
+        render_range,
+        |&(low, high)| {
+            day02::collect_invalid_numbers(low, high, |n| {
+                day02::smallest_repeating_pattern(n).is_some()
+            })
+        },
+        |&(low, high)| {
+            (low..=high)
+                .filter(|n| {
+                    let s = n.to_string();
+                    (1..s.len())
+                        .any(|len| s.len() % len == 0 && s == s[..len].repeat(s.len() / len))
+                })
+                .collect()
+        },
+    )
+}
+
+// day 7: a grid with a single source in the top row and splitters away from the edges
+
+fn tachyon_grid(rng: &mut StdRng) -> Vec<String> {
+    let width = rng.random_range(3..=9);
+    let height = rng.random_range(2..=10);
+    let source = rng.random_range(1..width - 1);
+
+    (0..height)
+        .map(|row| {
+            (0..width)
+                .map(|col| match (row, col) {
+                    (0, col) if col == source => 'S',
+                    (0, _) => '.',
+                    (_, col) if col == 0 || col == width - 1 => '.',
+                    _ if rng.random_bool(0.3) => '^',
+                    _ => '.',
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn shrink_grid(grid: &[String]) -> Vec<Vec<String>> {
+    let mut smaller = Vec::new();
+    // drop a row, the source row stays
+    for row in 1..grid.len() {
+        if grid.len() > 2 {
+            let mut g = grid.to_vec();
+            g.remove(row);
+            smaller.push(g);
+        }
+    }
+    // drop a column
+    let width = grid[0].len();
+    for col in 0..width {
+        if width > 1 {
+            smaller.push(
+                grid.iter()
+                    .map(|row| {
+                        let mut row = row.clone();
+                        row.remove(col);
+                        row
+                    })
+                    .collect(),
+            );
+        }
+    }
+    // clear a splitter
+    for (r, row) in grid.iter().enumerate() {
+        for (c, _) in row.match_indices('^') {
+            let mut g = grid.to_vec();
+            g[r].replace_range(c..c + 1, ".");
+            smaller.push(g);
+        }
+    }
+    smaller
+}
+
+fn day07_paths(rng: &mut StdRng, cases: usize) -> Option<Counterexample> {
+    let parse = |grid: &Vec<String>| day07::Grid::parse_grid(&grid.join("\n")).unwrap();
+    differential(
+        rng,
+        cases,
+        tachyon_grid,
+        |grid| shrink_grid(grid),
+        |grid| grid.join("\n"),
+        |grid| parse(grid).count_all_paths(),
+        |grid| parse(grid).trace_all_paths().len() as u64,
+    )
+}
+
+// day 10: a single machine, lights and joltages are indexed by the same counters
+
+#[derive(Clone, Debug)]
+struct Machine {
+    lights: Vec<bool>,
+    buttons: Vec<Vec<u32>>,
+    joltages: Vec<u32>,
+}
+
+impl Machine {
+    fn random(rng: &mut StdRng) -> Machine {
+        let counters = rng.random_range(2..=6);
+        let all: Vec<u32> = (0..counters as u32).collect();
+        let buttons = (0..rng.random_range(1..=6))
+            .map(|_| {
+                let count = rng.random_range(1..=counters);
+                let mut wired: Vec<u32> = all.choose_multiple(rng, count).copied().collect();
+                wired.sort_unstable();
+                wired
+            })
+            .collect();
+
+        Machine {
+            lights: (0..counters).map(|_| rng.random_bool(0.5)).collect(),
+            buttons,
+            joltages: (0..counters).map(|_| rng.random_range(0..=8)).collect(),
+        }
+    }
+
+    fn shrink(&self) -> Vec<Machine> {
+        let mut smaller = Vec::new();
+        for i in 0..self.buttons.len() {
+            let mut m = self.clone();
+            m.buttons.remove(i);
+            smaller.push(m);
+        }
+        for i in 0..self.lights.len() {
+            if self.lights[i] {
+                let mut m = self.clone();
+                m.lights[i] = false;
+                smaller.push(m);
+            }
+            if self.joltages[i] > 0 {
+                let mut m = self.clone();
+                m.joltages[i] -= 1;
+                smaller.push(m);
+            }
+        }
+        // drop the last counter along with the wiring to it
+        if self.lights.len() > 1 {
+            let last = self.lights.len() as u32 - 1;
+            let mut m = self.clone();
+            m.lights.pop();
+            m.joltages.pop();
+            for button in &mut m.buttons {
+                button.retain(|&c| c != last);
+            }
+            smaller.push(m);
+        }
+        smaller
+    }
+
+    fn render(&self) -> String {
+        self.problem_line().to_string()
+    }
+
+    fn problem_line(&self) -> day10::ProblemLine {
+        day10::ProblemLine {
+            indicator_lights: self.lights.iter().collect(),
+            wiring_schematics: self.buttons.clone(),
+            joltage_requirements: self.joltages.clone(),
+        }
+    }
+}
+
+fn day10_lights(rng: &mut StdRng, cases: usize) -> Option<Counterexample> {
+    differential(
+        rng,
+        cases,
+        Machine::random,
+        Machine::shrink,
+        Machine::render,
+        |m| {
+            m.problem_line()
+                .find_solutions()
+                .iter()
+                .map(|s| s.len())
+                .min()
+        },
+        |m| m.problem_line().find_optimal_solution().map(|s| s.len()),
+    )
+}
+
+fn day10_joltage(rng: &mut StdRng, cases: usize) -> Option<Counterexample> {
+    differential(
+        rng,
+        cases,
+        Machine::random,
+        Machine::shrink,
+        Machine::render,
+        |m| day10::find_minimum_presses_ilp(&m.joltages, &m.buttons),
+        |m| day10::find_minimum_presses(&m.joltages, &m.buttons),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_minimizes_counterexample() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let counterexample = differential(
+            &mut rng,
+            100,
+            |rng| {
+                let low = rng.random_range(0..40);
+                (low, low + rng.random_range(20..60))
+            },
+            shrink_range,
+            render_range,
+            |&(low, high)| (low..=high).filter(|n| n % 7 == 0 && *n != 49).count(),
+            |&(low, high)| (low..=high).filter(|n| n % 7 == 0).count(),
+        )
+        .unwrap();
+
+        assert_eq!(counterexample.input, "49-49");
+        assert_eq!(
+            (counterexample.fast.as_str(), counterexample.slow.as_str()),
+            ("0", "1")
+        );
+    }
+
+    #[test]
+    fn test_reports_panics() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let counterexample = differential(
+            &mut rng,
+            1,
+            |_| (1, 1),
+            shrink_range,
+            render_range,
+            |_| -> u32 { panic!("boom") },
+            |_| 1,
+        )
+        .unwrap();
+
+        assert_eq!(counterexample.fast, "panicked: boom");
+    }
+
+    #[test]
+    fn test_run_checks_restores_panic_hook() {
+        static REPORTED: AtomicBool = AtomicBool::new(false);
+        std::panic::set_hook(Box::new(|_| REPORTED.store(true, Ordering::Relaxed)));
+
+        assert!(run_checks(&["day02-twice".to_string()], 10, 0).unwrap());
+        let _ = std::panic::catch_unwind(|| panic!("after the checks"));
+        let _ = std::panic::take_hook();
+        assert!(REPORTED.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt;
+use std::path::PathBuf;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The error type shared by every day's parser and solvers, and the runner around them.
+#[derive(Debug)]
+pub enum Error {
+    /// The input (or another file) could not be read.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The input does not match the puzzle's format. Lines and columns are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// An error parsing or solving an input read from `source`, a file or stdin, with the
+    /// normalization that was applied to it before parsing (empty if it wasn't changed).
+    Input {
+        source: String,
+        normalization: String,
+        error: Box<Error>,
+    },
+    /// The input parsed, but the puzzle has no answer for it.
+    NoSolution(String),
+    /// The input parsed, but breaks an invariant the puzzle promises (e.g. a missing start node).
+    InvalidInput(String),
+    /// A solver was given up on because it ran for longer than it was allowed to.
+    TimedOut(String),
+}
+
+impl Error {
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Error {
+        Error::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Error {
+        Error::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// A parse error located at `fragment`, which must be a slice of `input`. Parsers can hand
+    /// over whatever slice they were looking at (a line, a nom remainder) without tracking
+    /// offsets themselves.
+    pub fn parse_at(input: &str, fragment: &str, message: impl Into<String>) -> Error {
+        let (line, column) = locate(input, fragment);
+        Error::parse(line, column, message)
+    }
+
+    /// Converts a nom error raised while parsing a slice of `input`.
+    pub fn from_nom(input: &str, err: nom::Err<nom::error::Error<&str>>) -> Error {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => Error::parse_at(
+                input,
+                e.input,
+                format!("unexpected input, expected {}", e.code.description()),
+            ),
+            nom::Err::Incomplete(_) => {
+                Error::parse_at(input, &input[input.len()..], "unexpected end of input")
+            }
+        }
+    }
+
+    /// Whether this is a parse error, with or without the input's context around it.
+    pub fn is_parse(&self) -> bool {
+        match self {
+            Error::Parse { .. } => true,
+            Error::Input { error, .. } => error.is_parse(),
+            _ => false,
+        }
+    }
+
+    /// Moves a parse error reported against a single line to that line's position in the input.
+    pub fn on_line(self, line: usize) -> Error {
+        match self {
+            Error::Parse {
+                column, message, ..
+            } => Error::Parse {
+                line,
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+/// The 1-based line and column at which `fragment` starts within `input`. Fragments that are not
+/// slices of `input` are reported at the end of it.
+fn locate(input: &str, fragment: &str) -> (usize, usize) {
+    let start = input.as_ptr() as usize;
+    let offset = (fragment.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|offset| *offset <= input.len())
+        .unwrap_or(input.len());
+
+    let consumed = &input[..offset];
+    let line = consumed.matches('\n').count() + 1;
+    let line_start = consumed.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = consumed[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "parse error at line {}, column {}: {}",
+                line, column, message
+            ),
+            Error::Input {
+                source,
+                normalization,
+                error,
+            } if normalization.is_empty() => write!(f, "{}: {}", source, error),
+            Error::Input {
+                source,
+                normalization,
+                error,
+            } => write!(f, "{} ({}): {}", source, normalization, error),
+            Error::NoSolution(message) => write!(f, "no solution: {}", message),
+            Error::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            Error::TimedOut(message) => write!(f, "timed out: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Input { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::character::complete::digit1;
+
+    #[test]
+    fn test_parse_at_locates_fragment() {
+        let input = "12\n34x5\n";
+        let err = Error::parse_at(input, &input[5..], "bad digit");
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 3: bad digit"
+        );
+    }
+
+    #[test]
+    fn test_from_nom() {
+        let input = "12\nab";
+        let line = input.lines().nth(1).unwrap();
+        let err = digit1::<_, nom::error::Error<&str>>(line).unwrap_err();
+
+        match Error::from_nom(input, err) {
+            Error::Parse { line, column, .. } => assert_eq!((line, column), (2, 1)),
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_on_line() {
+        let err = Error::parse(1, 4, "oops").on_line(7);
+        assert_eq!(err.to_string(), "parse error at line 7, column 4: oops");
+    }
+}
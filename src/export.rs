@@ -0,0 +1,512 @@
+// Pictures of the visualized days for write-ups, written as PPM, PNG or SVG depending on the file
+// extension: the wave each roll is removed in on day 4, the number of paths through every beam on
+// day 7, the polygon with the largest rectangle inside it on day 9 and the packing of a region on
+// day 12.
+
+use std::io::Write;
+use std::path::Path;
+
+use crate::day04::{Day04, Floor, Tile};
+use crate::day07::{Day07, Grid};
+use crate::day09::{Day09, FloorPlan};
+use crate::day12::{self, Day12, ProblemData, Step};
+use crate::error::{Error, Result};
+use crate::geometry::{Point, Rect};
+use crate::solution::Solution;
+use crate::visualize::DAYS;
+
+/// The search on day 12 is given far more room than the animation, there is no frame per step.
+pub const MAX_STEPS: usize = 1_000_000;
+
+/// Cell pictures are scaled up to about this many pixels along their longer side.
+const TARGET_PIXELS: usize = 1600;
+const MAX_CELL_PIXELS: usize = 16;
+
+const BACKGROUND: Rgb = [24, 24, 24];
+const STILL_THERE: Rgb = [110, 110, 110];
+const SPLITTER: Rgb = [235, 235, 235];
+
+pub type Rgb = [u8; 3];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Result<Format> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("ppm") => Ok(Format::Ppm),
+            Some("png") => Ok(Format::Png),
+            Some("svg") => Ok(Format::Svg),
+            _ => Err(Error::InvalidInput(format!(
+                "can't tell the format of {}, use a .ppm, .png or .svg file",
+                path.display()
+            ))),
+        }
+    }
+}
+
+pub enum Picture {
+    /// A grid with one colour per cell, drawn as squares.
+    Cells {
+        caption: String,
+        cells: Vec<Vec<Rgb>>,
+    },
+    /// A polygon, in puzzle coordinates, and a rectangle inside it.
+    Polygon {
+        caption: String,
+        points: Vec<Point>,
+        rectangle: Option<Rect>,
+    },
+}
+
+/// Writes the picture of a day to `path`, in the format its extension asks for.
+pub fn export(day: u8, input: &str, region: usize, path: &Path) -> Result<()> {
+    let format = Format::from_path(path)?;
+    let picture = picture(day, input, region)?;
+
+    let mut bytes = Vec::new();
+    write(&picture, format, &mut bytes).map_err(|e| Error::io(path, e))?;
+    std::fs::write(path, bytes).map_err(|e| Error::io(path, e))
+}
+
+pub fn picture(day: u8, input: &str, region: usize) -> Result<Picture> {
+    match day {
+        4 => Ok(removal_heatmap(&Day04::parse(input)?)),
+        7 => path_heatmap(Day07::parse(input)?),
+        9 => Ok(largest_rectangle(&Day09::parse(input)?)),
+        12 => packed_region(&Day12::parse(input)?, region),
+        _ => {
+            let days: Vec<String> = DAYS.iter().map(|d| d.to_string()).collect();
+            Err(Error::InvalidInput(format!(
+                "there is no picture for day {}, only for days {}",
+                day,
+                days.join(", ")
+            )))
+        }
+    }
+}
+
+// day 4: each roll coloured by the wave it's removed in, early waves dark and late ones bright
+
+fn removal_heatmap(floor: &Floor) -> Picture {
+    let mut cells: Vec<Vec<Rgb>> = floor
+        .tiles()
+        .rows()
+        .map(|row| {
+            row.iter()
+                .map(|tile| match tile {
+                    Tile::EmptySpace => BACKGROUND,
+                    _ => STILL_THERE,
+                })
+                .collect()
+        })
+        .collect();
+
+    let waves = floor.removal_waves();
+    for (i, wave) in waves.iter().enumerate() {
+        let colour = heat(i as f64 / (waves.len() - 1).max(1) as f64);
+        for &(r, c) in wave {
+            cells[r][c] = colour;
+        }
+    }
+
+    let removed: usize = waves.iter().map(|wave| wave.len()).sum();
+    Picture::Cells {
+        caption: format!("{} rolls removed in {} waves", removed, waves.len()),
+        cells,
+    }
+}
+
+// day 7: every square a beam passes through, coloured by the number of paths from it to the bottom
+// on a log scale
+
+fn path_heatmap(mut grid: Grid) -> Result<Picture> {
+    let counts = grid.path_counts();
+    grid.trace_tachyons_down()?;
+
+    let beams = |r: usize, c: usize| grid.has_beam(r, c) && grid.glyph(r, c) != '^';
+    let most = (0..grid.height())
+        .flat_map(|r| (0..grid.width()).map(move |c| (r, c)))
+        .filter(|&(r, c)| beams(r, c))
+        .map(|(r, c)| counts[(r, c)])
+        .max()
+        .unwrap_or(0);
+    let scale = (most as f64).ln_1p().max(f64::MIN_POSITIVE);
+
+    let cells = (0..grid.height())
+        .map(|r| {
+            (0..grid.width())
+                .map(|c| match grid.glyph(r, c) {
+                    '^' => SPLITTER,
+                    _ if beams(r, c) => heat((counts[(r, c)] as f64).ln_1p() / scale),
+                    _ => BACKGROUND,
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(Picture::Cells {
+        caption: format!(
+            "{} paths, up to {} through a single square",
+            grid.count_all_paths(),
+            most
+        ),
+        cells,
+    })
+}
+
+// day 9: the polygon and the largest rectangle inside it
+
+fn largest_rectangle(plan: &FloorPlan) -> Picture {
+    let rectangle = plan.largest_valid_rectangle();
+    let caption = match rectangle {
+        Some(rect) => format!(
+            "the largest rectangle, {},{} to {},{}, covers {} tiles",
+            rect.min.x,
+            rect.min.y,
+            rect.max.x,
+            rect.max.y,
+            rect.area()
+        ),
+        None => "there is no rectangle inside the polygon".to_string(),
+    };
+    Picture::Polygon {
+        caption,
+        points: plan.points().to_vec(),
+        rectangle,
+    }
+}
+
+// day 12: the pieces left in the region when the search succeeds, one colour each
+
+fn packed_region(data: &ProblemData, region: usize) -> Result<Picture> {
+    let region_index = region;
+    let region = data.regions.get(region).ok_or_else(|| {
+        Error::InvalidInput(format!(
+            "there is no region {}, the input has {} regions",
+            region,
+            data.regions.len()
+        ))
+    })?;
+
+    let (steps, fits) = day12::trace_region(region, &data.shapes, MAX_STEPS);
+    match fits {
+        Some(true) => {}
+        Some(false) => {
+            return Err(Error::InvalidInput(format!(
+                "the pieces don't fit in region {}, there is no packing to draw",
+                region_index
+            )));
+        }
+        None => {
+            return Err(Error::InvalidInput(format!(
+                "gave up packing region {} after {} steps",
+                region_index, MAX_STEPS
+            )));
+        }
+    }
+
+    // a removal always takes back the last piece placed
+    let mut pieces = Vec::new();
+    for step in steps {
+        match step {
+            Step::Place { cells, .. } => pieces.push(cells),
+            Step::Remove { .. } => {
+                pieces.pop();
+            }
+        }
+    }
+
+    let mut cells = vec![vec![BACKGROUND; region.width]; region.height];
+    for (i, piece) in pieces.iter().enumerate() {
+        for p in piece {
+            cells[p.y as usize][p.x as usize] = piece_colour(i);
+        }
+    }
+    Ok(Picture::Cells {
+        caption: format!(
+            "region {}: {} pieces packed in {}x{}",
+            region_index,
+            pieces.len(),
+            region.width,
+            region.height
+        ),
+        cells,
+    })
+}
+
+// ============ Colours ============
+
+/// Dark blue through purple and orange to pale yellow, for `t` from 0 to 1.
+fn heat(t: f64) -> Rgb {
+    const STOPS: [Rgb; 4] = [[20, 24, 82], [128, 34, 140], [232, 96, 40], [250, 236, 140]];
+    let t = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let i = (t as usize).min(STOPS.len() - 2);
+    let f = t - i as f64;
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * f).round() as u8;
+    [
+        mix(STOPS[i][0], STOPS[i + 1][0]),
+        mix(STOPS[i][1], STOPS[i + 1][1]),
+        mix(STOPS[i][2], STOPS[i + 1][2]),
+    ]
+}
+
+/// Consecutive pieces are a golden angle apart in hue, so no two nearby pieces look alike.
+fn piece_colour(piece: usize) -> Rgb {
+    let hue = (piece as f64 * 0.381_966).fract() * 6.0;
+    let (saturation, value) = (0.6, 0.9);
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as usize {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let channel = |c: f64| ((c + value - chroma) * 255.0).round() as u8;
+    [channel(r), channel(g), channel(b)]
+}
+
+fn hex(colour: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", colour[0], colour[1], colour[2])
+}
+
+// ============ Writers ============
+
+pub fn write(picture: &Picture, format: Format, out: &mut impl Write) -> std::io::Result<()> {
+    match (picture, format) {
+        (Picture::Cells { caption, cells }, Format::Ppm) => write_ppm(caption, cells, out),
+        (Picture::Cells { caption, cells }, Format::Png) => write_png(caption, cells, out),
+        (Picture::Cells { caption, cells }, Format::Svg) => write_cells_svg(caption, cells, out),
+        (Picture::Polygon { .. }, Format::Svg) => write_polygon_svg(picture, out),
+        (Picture::Polygon { .. }, _) => Err(std::io::Error::other(
+            "the polygon is drawn with lines, export it as an .svg file",
+        )),
+    }
+}
+
+fn cell_pixels(cells: &[Vec<Rgb>]) -> usize {
+    let longest = cells.len().max(cells.first().map_or(0, |row| row.len()));
+    (TARGET_PIXELS / longest.max(1)).clamp(1, MAX_CELL_PIXELS)
+}
+
+/// The cells scaled up to squares, as rows of RGB bytes.
+fn pixels(cells: &[Vec<Rgb>]) -> (usize, usize, Vec<u8>) {
+    let scale = cell_pixels(cells);
+    let width = cells.first().map_or(0, |row| row.len()) * scale;
+    let mut bytes = Vec::with_capacity(width * cells.len() * scale * 3);
+    for row in cells {
+        let line: Vec<u8> = row
+            .iter()
+            .flat_map(|colour| std::iter::repeat_n(colour, scale).flatten())
+            .copied()
+            .collect();
+        for _ in 0..scale {
+            bytes.extend_from_slice(&line);
+        }
+    }
+    (width, cells.len() * scale, bytes)
+}
+
+fn write_ppm(caption: &str, cells: &[Vec<Rgb>], out: &mut impl Write) -> std::io::Result<()> {
+    let (width, height, bytes) = pixels(cells);
+    write!(out, "P6\n# {}\n{} {}\n255\n", caption, width, height)?;
+    out.write_all(&bytes)
+}
+
+fn write_png(caption: &str, cells: &[Vec<Rgb>], out: &mut impl Write) -> std::io::Result<()> {
+    let (width, height, bytes) = pixels(cells);
+    let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .add_text_chunk("Title".to_string(), caption.to_string())
+        .map_err(std::io::Error::other)?;
+    let mut writer = encoder.write_header().map_err(std::io::Error::other)?;
+    writer
+        .write_image_data(&bytes)
+        .map_err(std::io::Error::other)?;
+    writer.finish().map_err(std::io::Error::other)
+}
+
+fn write_cells_svg(caption: &str, cells: &[Vec<Rgb>], out: &mut impl Write) -> std::io::Result<()> {
+    let scale = cell_pixels(cells);
+    let (width, height) = (cells.first().map_or(0, |row| row.len()), cells.len());
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+        width * scale,
+        height * scale,
+        width,
+        height
+    )?;
+    writeln!(out, "<title>{}</title>", escape(caption))?;
+    // one rectangle per run of cells of the same colour
+    for (y, row) in cells.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let run = row[x..].iter().take_while(|&&c| c == row[x]).count();
+            writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="{}" height="1" fill="{}"/>"#,
+                x,
+                y,
+                run,
+                hex(row[x])
+            )?;
+            x += run;
+        }
+    }
+    writeln!(out, "</svg>")
+}
+
+fn write_polygon_svg(picture: &Picture, out: &mut impl Write) -> std::io::Result<()> {
+    let Picture::Polygon {
+        caption,
+        points,
+        rectangle,
+    } = picture
+    else {
+        unreachable!("only called for polygons");
+    };
+
+    let bounds = Rect::bounding(points.iter())
+        .unwrap_or(Rect::from_corners(Point::default(), Point::default()));
+    let (min_x, max_x) = (bounds.min.x as i64, bounds.max.x as i64);
+    let (min_y, max_y) = (bounds.min.y as i64, bounds.max.y as i64);
+    let span = (max_x - min_x).max(max_y - min_y).max(1);
+    let margin = span / 50 + 1;
+    let (view_width, view_height) = (max_x - min_x + 2 * margin, max_y - min_y + 2 * margin);
+    let width = TARGET_PIXELS as i64 * view_width / view_width.max(view_height);
+    let height = TARGET_PIXELS as i64 * view_height / view_width.max(view_height);
+
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+        width,
+        height,
+        min_x - margin,
+        min_y - margin,
+        view_width,
+        view_height
+    )?;
+    writeln!(out, "<title>{}</title>", escape(caption))?;
+
+    let corners: Vec<String> = points.iter().map(|p| format!("{},{}", p.x, p.y)).collect();
+    writeln!(
+        out,
+        r##"<polygon points="{}" fill="#c8e6c9" stroke="#2e7d32" stroke-width="2" vector-effect="non-scaling-stroke"/>"##,
+        corners.join(" ")
+    )?;
+    if let Some(rect) = rectangle {
+        writeln!(
+            out,
+            r##"<rect x="{}" y="{}" width="{}" height="{}" fill="#1565c0" fill-opacity="0.45" stroke="#0d47a1" stroke-width="2" vector-effect="non-scaling-stroke"/>"##,
+            rect.min.x,
+            rect.min.y,
+            rect.max.x - rect.min.x,
+            rect.max.y - rect.min.y
+        )?;
+    }
+    // the red tiles, a little dot at every corner of the polygon
+    let radius = span as f64 / 400.0;
+    for p in points {
+        writeln!(
+            out,
+            r##"<circle cx="{}" cy="{}" r="{}" fill="#d32f2f"/>"##,
+            p.x, p.y, radius
+        )?;
+    }
+    writeln!(out, "</svg>")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(day: u8) -> &'static str {
+        crate::registry::find(day).unwrap().examples()[0].input
+    }
+
+    fn render(day: u8, format: Format) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write(&picture(day, example(day), 0).unwrap(), format, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            Format::from_path(Path::new("a/b.PNG")).unwrap(),
+            Format::Png
+        );
+        assert_eq!(Format::from_path(Path::new("b.svg")).unwrap(), Format::Svg);
+        assert!(Format::from_path(Path::new("b.jpg")).is_err());
+        assert!(Format::from_path(Path::new("b")).is_err());
+    }
+
+    #[test]
+    fn test_removal_heatmap() {
+        let Picture::Cells { caption, cells } = picture(4, example(4), 0).unwrap() else {
+            panic!("day 4 is a grid");
+        };
+        assert_eq!(caption, "43 rolls removed in 9 waves");
+        assert_eq!((cells.len(), cells[0].len()), (10, 10));
+        // the first wave is the darkest, empty space is the background
+        assert_eq!(cells[0][0], BACKGROUND);
+        assert_eq!(cells[0][2], heat(0.0));
+    }
+
+    #[test]
+    fn test_ppm() {
+        let bytes = render(7, Format::Ppm);
+        // the example is 15 squares wide and 16 high, drawn 16 pixels to a square
+        let header = b"P6\n# 40 paths, up to 40 through a single square\n240 256\n255\n";
+        assert!(bytes.starts_with(header));
+        assert_eq!(bytes.len(), header.len() + 240 * 256 * 3);
+    }
+
+    #[test]
+    fn test_png() {
+        let bytes = render(12, Format::Png);
+        let decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+        let reader = decoder.read_info().unwrap();
+        // the first region is 4x4
+        assert_eq!((reader.info().width, reader.info().height), (64, 64));
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = String::from_utf8(render(9, Format::Svg)).unwrap();
+        assert!(svg.contains("covers 24 tiles"));
+        assert_eq!(svg.matches("<circle").count(), 8);
+
+        let mut bytes = Vec::new();
+        let picture = picture(9, example(9), 0).unwrap();
+        assert!(write(&picture, Format::Png, &mut bytes).is_err());
+    }
+
+    #[test]
+    fn test_piece_colours_differ() {
+        let colours: Vec<Rgb> = (0..8).map(piece_colour).collect();
+        for (i, a) in colours.iter().enumerate() {
+            assert!(!colours[i + 1..].contains(a));
+        }
+    }
+}
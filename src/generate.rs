@@ -0,0 +1,364 @@
+// Synthetic puzzle inputs for stress testing the solvers. Every generator produces an input in the
+// same format as the real one, sized by `size` (what it counts depends on the day) and fully
+// determined by `seed`, so a slow or failing input can be regenerated from its command line.
+
+use std::collections::HashSet;
+
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, SeedableRng};
+
+use crate::error::{Error, Result};
+
+pub struct Generator {
+    pub day: u8,
+    /// Roughly the size of the real input.
+    pub default_size: usize,
+    generate: fn(&mut StdRng, usize) -> String,
+}
+
+pub const GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        default_size: 4000,
+        generate: dial_rotations,
+    },
+    Generator {
+        day: 2,
+        default_size: 30,
+        generate: id_ranges,
+    },
+    Generator {
+        day: 3,
+        default_size: 200,
+        generate: digit_banks,
+    },
+    Generator {
+        day: 4,
+        default_size: 140,
+        generate: paper_roll_floor,
+    },
+    Generator {
+        day: 9,
+        default_size: 500,
+        generate: rectilinear_polygon,
+    },
+    Generator {
+        day: 11,
+        default_size: 600,
+        generate: device_graph,
+    },
+    Generator {
+        day: 12,
+        default_size: 1000,
+        generate: shapes_and_regions,
+    },
+];
+
+pub fn find(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+/// Generates an input for the day, `None` uses the generator's default size.
+pub fn generate(day: u8, size: Option<usize>, seed: u64) -> Result<String> {
+    let generator = find(day).ok_or_else(|| {
+        let days: Vec<String> = GENERATORS.iter().map(|g| g.day.to_string()).collect();
+        Error::InvalidInput(format!(
+            "there is no input generator for day {}, only for days {}",
+            day,
+            days.join(", ")
+        ))
+    })?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    Ok((generator.generate)(
+        &mut rng,
+        size.unwrap_or(generator.default_size),
+    ))
+}
+
+// Mostly short turns with the odd turn of several full revolutions, like the real input.
+fn dial_rotations(rng: &mut StdRng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let dir = if rng.random_bool(0.5) { 'L' } else { 'R' };
+        let steps = if rng.random_bool(0.9) {
+            rng.random_range(1..100)
+        } else {
+            rng.random_range(100..1000)
+        };
+        out.push_str(&format!("{}{}\n", dir, steps));
+    }
+    out
+}
+
+// Ranges of IDs with up to 10 digits. The solver walks every ID in a range, so ranges are kept
+// about as wide as the real ones.
+fn id_ranges(rng: &mut StdRng, size: usize) -> String {
+    let ranges: Vec<String> = (0..size)
+        .map(|_| {
+            let digits = rng.random_range(1..=10);
+            let low: u64 = rng.random_range(10u64.pow(digits - 1)..10u64.pow(digits));
+            let width = rng.random_range(0..=(low / 10).clamp(10, 200_000));
+            format!("{}-{}", low, low + width)
+        })
+        .collect();
+    ranges.join(",") + "\n"
+}
+
+// Banks of 100 batteries with joltages 1-9.
+fn digit_banks(rng: &mut StdRng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        for _ in 0..100 {
+            out.push(char::from(b'0' + rng.random_range(1..=9)));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// A square floor with paper rolls on about 60% of the tiles.
+fn paper_roll_floor(rng: &mut StdRng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            out.push(if rng.random_bool(0.6) { '@' } else { '.' });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// A simple rectilinear polygon, listed in order. The polygon is split into columns, each spanning
+// from a bottom edge below the middle line to a top edge above it, so walking the tops left to
+// right and the bottoms back again never crosses itself. Every column adds four red tiles.
+fn rectilinear_polygon(rng: &mut StdRng, size: usize) -> String {
+    const MIDDLE: i32 = 50_000;
+
+    let columns = (size / 4).max(1);
+    let step = (90_000 / columns as i32).max(1);
+
+    let mut xs = vec![rng.random_range(1_000..5_000)];
+    for _ in 0..columns {
+        let last = *xs.last().unwrap();
+        xs.push(last + rng.random_range(1..=step));
+    }
+
+    // neighbouring columns get different heights, otherwise the shared corner isn't a corner
+    let heights = |rng: &mut StdRng, range: std::ops::Range<i32>| {
+        let mut values: Vec<i32> = Vec::with_capacity(columns);
+        while values.len() < columns {
+            let value = rng.random_range(range.clone());
+            if values.last() != Some(&value) {
+                values.push(value);
+            }
+        }
+        values
+    };
+    let tops = heights(rng, MIDDLE + 1..MIDDLE + 45_000);
+    let bottoms = heights(rng, MIDDLE - 45_000..MIDDLE);
+
+    let mut points = Vec::with_capacity(columns * 4);
+    for i in 0..columns {
+        points.push((xs[i], tops[i]));
+        points.push((xs[i + 1], tops[i]));
+    }
+    for i in (0..columns).rev() {
+        points.push((xs[i + 1], bottoms[i]));
+        points.push((xs[i], bottoms[i]));
+    }
+
+    points
+        .iter()
+        .map(|(x, y)| format!("{},{}\n", x, y))
+        .collect()
+}
+
+// A DAG of devices with three letter names. Devices are laid out in a topological order and only
+// connect to the next few devices, so `svr` comes first, `fft` and `dac` sit in between and every
+// path ends at `out`. Most devices have a single output so the path counts fit in a u64 at the
+// default size. `you` is placed near the end to keep the number of paths from it, which
+// part 1 lists one by one, manageable.
+fn device_graph(rng: &mut StdRng, size: usize) -> String {
+    const FAN_OUT: usize = 8;
+
+    let size = size.max(6);
+    let mut taken: HashSet<String> = ["svr", "fft", "dac", "you", "out"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let mut names: Vec<String> = Vec::with_capacity(size + 1);
+    while names.len() < size {
+        let name: String = (0..3)
+            .map(|_| char::from(b'a' + rng.random_range(0..26)))
+            .collect();
+        if taken.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    let (fft, dac) = (size / 3, 2 * size / 3);
+    names[0] = "svr".to_string();
+    names[fft] = "fft".to_string();
+    names[dac] = "dac".to_string();
+    names[size.saturating_sub(20).max(2 * size / 3 + 1)] = "you".to_string();
+    names.push("out".to_string());
+
+    let mut lines: Vec<String> = (0..size)
+        .map(|i| {
+            let mut last = (i + FAN_OUT).min(size);
+            // most devices can't skip past fft and dac, so plenty of paths go through both
+            for required in [fft, dac] {
+                if i < required && last > required && rng.random_bool(0.8) {
+                    last = required;
+                }
+            }
+            let candidates: Vec<usize> = (i + 1..=last).collect();
+            // mostly a single output, the number of paths grows with every fork
+            let count = match rng.random_range(0..20) {
+                0 => 3,
+                1..=4 => 2,
+                _ => 1,
+            }
+            .min(candidates.len());
+            let outputs: Vec<&str> = candidates
+                .choose_multiple(rng, count)
+                .map(|&j| names[j].as_str())
+                .collect();
+            format!("{}: {}\n", names[i], outputs.join(" "))
+        })
+        .collect();
+    lines.shuffle(rng);
+    lines.concat()
+}
+
+// Six 3x3 shapes followed by regions. About half of the regions are packed loosely enough to
+// fit, the rest ask for more cells than the region has.
+fn shapes_and_regions(rng: &mut StdRng, size: usize) -> String {
+    const SHAPES: usize = 6;
+
+    let mut out = String::new();
+    let mut cells = Vec::with_capacity(SHAPES);
+    for i in 0..SHAPES {
+        let shape = random_shape(rng);
+        cells.push(shape.iter().filter(|&&filled| filled).count());
+
+        out.push_str(&format!("{}:\n", i));
+        for row in shape.chunks(3) {
+            let row: String = row.iter().map(|&c| if c { '#' } else { '.' }).collect();
+            out.push_str(&row);
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+
+    for _ in 0..size {
+        let width = rng.random_range(35..=50);
+        let height = rng.random_range(35..=50);
+        let fill = if rng.random_bool(0.5) {
+            rng.random_range(0.5..0.7)
+        } else {
+            rng.random_range(1.05..1.3)
+        };
+
+        let mut counts = [0usize; SHAPES];
+        let mut filled = 0;
+        while (filled as f64) < (width * height) as f64 * fill {
+            let shape = rng.random_range(0..SHAPES);
+            counts[shape] += 1;
+            filled += cells[shape];
+        }
+
+        let counts: Vec<String> = counts.iter().map(|c| c.to_string()).collect();
+        out.push_str(&format!("{}x{}: {}\n", width, height, counts.join(" ")));
+    }
+    out
+}
+
+// A shape of 5 to 7 connected cells in a 3x3 box, grown from the centre.
+fn random_shape(rng: &mut StdRng) -> [bool; 9] {
+    let target = rng.random_range(5..=7);
+    let mut shape = [false; 9];
+    shape[4] = true;
+
+    let mut filled = 1;
+    while filled < target {
+        let cell = rng.random_range(0..9);
+        let (x, y) = (cell % 3, cell / 3);
+        let touches_shape = [(0, 1), (2, 1), (1, 0), (1, 2)]
+            .iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .any(|(nx, ny)| {
+                (1..=3).contains(&nx) && (1..=3).contains(&ny) && shape[(ny - 1) * 3 + nx - 1]
+            });
+        if !shape[cell] && touches_shape {
+            shape[cell] = true;
+            filled += 1;
+        }
+    }
+    shape
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use crate::runner;
+    use crate::solution::Part;
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        for generator in GENERATORS {
+            assert_eq!(
+                generate(generator.day, Some(10), 7).unwrap(),
+                generate(generator.day, Some(10), 7).unwrap()
+            );
+        }
+        assert_ne!(
+            generate(1, Some(10), 1).unwrap(),
+            generate(1, Some(10), 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for generator in GENERATORS {
+            let day = registry::find(generator.day).unwrap();
+            for seed in 0..3 {
+                let input = generate(generator.day, Some(6), seed).unwrap();
+                let run = runner::run(day, &input, &Part::BOTH);
+                assert!(
+                    run.is_ok(),
+                    "day {} seed {}: {}",
+                    generator.day,
+                    seed,
+                    run.err().unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rectilinear_polygon() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let input = rectilinear_polygon(&mut rng, 40);
+        let points: Vec<(i32, i32)> = input
+            .lines()
+            .map(|line| {
+                let (x, y) = line.split_once(',').unwrap();
+                (x.parse().unwrap(), y.parse().unwrap())
+            })
+            .collect();
+
+        assert_eq!(points.len(), 40);
+        // every edge, including the closing one, is horizontal or vertical
+        for (i, a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            assert!(a != &b && (a.0 == b.0 || a.1 == b.1));
+        }
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        assert!(generate(5, None, 0).is_err());
+    }
+}
@@ -0,0 +1,387 @@
+// Integer geometry shared by the days that work with coordinates: 2D and 3D points, axis-aligned
+// rectangles and polygons whose edges run along the axes. In 2D, y grows downwards the way the
+// puzzles print their maps.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point3D {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+// The component-wise operators and distances are the same for both point types.
+macro_rules! point_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl $point {
+            /// The distance moving along the axes only.
+            pub fn manhattan(&self, other: &$point) -> u64 {
+                0 $(+ (self.$field as i64 - other.$field as i64).unsigned_abs())+
+            }
+
+            /// The square of the straight line distance, exact where `distance` would round.
+            pub fn distance_squared(&self, other: &$point) -> u64 {
+                0 $(+ (self.$field as i64 - other.$field as i64).pow(2) as u64)+
+            }
+
+            /// The straight line (Euclidean) distance.
+            pub fn distance(&self, other: &$point) -> f64 {
+                (self.distance_squared(other) as f64).sqrt()
+            }
+        }
+
+        impl Add for $point {
+            type Output = $point;
+
+            fn add(self, other: $point) -> $point {
+                $point { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $point;
+
+            fn sub(self, other: $point) -> $point {
+                $point { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = $point;
+
+            fn neg(self) -> $point {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+
+        impl Mul<i32> for $point {
+            type Output = $point;
+
+            fn mul(self, factor: i32) -> $point {
+                $point { $($field: self.$field * factor),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: $point) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: $point) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+point_ops!(Point { x, y });
+point_ops!(Point3D { x, y, z });
+
+impl Point {
+    pub const fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    /// A quarter turn anticlockwise around the origin, as drawn with y pointing down.
+    pub fn rotate_left(self) -> Point {
+        Point::new(self.y, -self.x)
+    }
+
+    /// A quarter turn clockwise around the origin, as drawn with y pointing down.
+    pub fn rotate_right(self) -> Point {
+        Point::new(-self.y, self.x)
+    }
+
+    /// Mirrored left to right, across the y axis.
+    pub fn flip_horizontal(self) -> Point {
+        Point::new(-self.x, self.y)
+    }
+
+    /// Mirrored top to bottom, across the x axis.
+    pub fn flip_vertical(self) -> Point {
+        Point::new(self.x, -self.y)
+    }
+}
+
+impl Point3D {
+    pub const fn new(x: i32, y: i32, z: i32) -> Point3D {
+        Point3D { x, y, z }
+    }
+}
+
+/// An axis-aligned rectangle of whole tiles, `min` and `max` are its top left and bottom right
+/// tiles and both belong to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    /// The rectangle with opposite corners `a` and `b`, whichever corners they are.
+    pub fn from_corners(a: Point, b: Point) -> Rect {
+        Rect {
+            min: Point::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// The smallest rectangle holding every point, `None` if there are none.
+    pub fn bounding<'a>(points: impl IntoIterator<Item = &'a Point>) -> Option<Rect> {
+        points.into_iter().fold(None, |rect, &p| {
+            Some(match rect {
+                None => Rect::from_corners(p, p),
+                Some(rect) => Rect {
+                    min: Point::new(rect.min.x.min(p.x), rect.min.y.min(p.y)),
+                    max: Point::new(rect.max.x.max(p.x), rect.max.y.max(p.y)),
+                },
+            })
+        })
+    }
+
+    pub fn width(&self) -> u64 {
+        (self.max.x as i64 - self.min.x as i64) as u64 + 1
+    }
+
+    pub fn height(&self) -> u64 {
+        (self.max.y as i64 - self.min.y as i64) as u64 + 1
+    }
+
+    /// The number of tiles, edges included.
+    pub fn area(&self) -> u64 {
+        self.width() * self.height()
+    }
+
+    /// Top left, top right, bottom right and bottom left.
+    pub fn corners(&self) -> [Point; 4] {
+        [
+            self.min,
+            Point::new(self.max.x, self.min.y),
+            self.max,
+            Point::new(self.min.x, self.max.y),
+        ]
+    }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The tiles both rectangles share, `None` if they share none.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let min = Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        (min.x <= max.x && min.y <= max.y).then_some(Rect { min, max })
+    }
+
+    /// Whether `other` reaches strictly inside this rectangle, touching its edges doesn't count.
+    /// `other` may be a single row or column, like the edge of a polygon.
+    pub fn overlaps_interior(&self, other: &Rect) -> bool {
+        other.min.x < self.max.x
+            && other.max.x > self.min.x
+            && other.min.y < self.max.y
+            && other.max.y > self.min.y
+    }
+}
+
+/// A closed polygon whose edges all run along the axes, given by its corners in order. The last
+/// corner connects back to the first.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point>) -> Polygon {
+        Polygon { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    /// Every edge as its two ends, the closing edge back to the first corner last.
+    pub fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |i| (self.vertices[i], self.vertices[(i + 1) % n]))
+    }
+
+    pub fn bounding_box(&self) -> Option<Rect> {
+        Rect::bounding(&self.vertices)
+    }
+
+    /// The length of the boundary.
+    pub fn perimeter(&self) -> u64 {
+        self.edges().map(|(a, b)| a.manhattan(&b)).sum()
+    }
+
+    /// Twice the enclosed area, by the shoelace formula. Doubled so it stays a whole number, with
+    /// the perimeter it gives the number of tiles inside through Pick's theorem.
+    pub fn double_area(&self) -> u64 {
+        self.edges()
+            .map(|(a, b)| a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64)
+            .sum::<i64>()
+            .unsigned_abs()
+    }
+
+    /// Whether the point is on the boundary or inside it.
+    //
+    // Ray casting: a ray from the point to +∞ along x crosses the boundary an odd number of
+    // times from the inside and an even number of times from the outside. Only the vertical
+    // edges can cross it, the horizontal ones are only checked for the point lying on them.
+    //
+    //                        │             │
+    //       • ──────────────►│────────────►│──────────────► (2 crossings = even = outside)
+    //                        │  • ────────►│──────────────► (1 crossing = odd = inside)
+    //                        │             │  • ──────────► (0 crossings = even = outside)
+    //                        └─────────────┘
+    pub fn contains(&self, point: &Point) -> bool {
+        let (px, py) = (point.x, point.y);
+        let mut inside = false;
+
+        for (a, b) in self.edges() {
+            if a.x == b.x {
+                if px == a.x && py >= a.y.min(b.y) && py <= a.y.max(b.y) {
+                    return true;
+                }
+                // only one end of the edge counts as covering the ray's row, so a ray through a
+                // corner crosses once
+                if (a.y > py) != (b.y > py) && px < a.x {
+                    inside = !inside;
+                }
+            } else if a.y == b.y && py == a.y && px >= a.x.min(b.x) && px <= a.x.max(b.x) {
+                return true;
+            }
+        }
+
+        inside
+    }
+
+    /// Whether every tile of the rectangle is on the boundary or inside it.
+    //
+    // All four corners can be inside while an edge still cuts through the rectangle:
+    //       a●━━━━━━━━━━━━━━●c
+    //        ┃              ┃
+    //        ┃    ┌────┐    ┃  ← the edge cuts through the interior
+    //        ┃    │    │    ┃
+    //       d●━━━━┘    └━━━━●b
+    pub fn contains_rect(&self, rect: &Rect) -> bool {
+        rect.corners().iter().all(|corner| self.contains(corner))
+            && !self
+                .edges()
+                .filter(|(a, b)| a.x == b.x || a.y == b.y)
+                .any(|(a, b)| rect.overlaps_interior(&Rect::from_corners(a, b)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the polygon of the day 9 example
+    fn polygon() -> Polygon {
+        let corners = [
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ];
+        Polygon::new(corners.iter().map(|&(x, y)| Point::new(x, y)).collect())
+    }
+
+    #[test]
+    fn test_point_ops_and_metrics() {
+        let (a, b) = (Point::new(1, 2), Point::new(4, -2));
+        assert_eq!(a + b, Point::new(5, 0));
+        assert_eq!(a - b, Point::new(-3, 4));
+        assert_eq!(-a * 2, Point::new(-2, -4));
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.distance_squared(&b), 25);
+        assert_eq!(a.distance(&b), 5.0);
+
+        let mut c = Point3D::new(1, 2, 3);
+        c += Point3D::new(1, 1, 1);
+        assert_eq!(c, Point3D::new(2, 3, 4));
+        assert_eq!(c.distance_squared(&Point3D::default()), 29);
+        assert_eq!(c.manhattan(&Point3D::new(-2, 3, 4)), 4);
+    }
+
+    #[test]
+    fn test_rotations() {
+        let p = Point::new(2, 1);
+        assert_eq!(p.rotate_left(), Point::new(1, -2));
+        assert_eq!(p.rotate_right().rotate_left(), p);
+        assert_eq!(p.rotate_left().rotate_left(), -p);
+        assert_eq!(p.flip_horizontal(), Point::new(-2, 1));
+        assert_eq!(p.flip_vertical(), Point::new(2, -1));
+    }
+
+    #[test]
+    fn test_rect() {
+        let rect = Rect::from_corners(Point::new(9, 5), Point::new(2, 3));
+        assert_eq!((rect.min, rect.max), (Point::new(2, 3), Point::new(9, 5)));
+        assert_eq!((rect.width(), rect.height(), rect.area()), (8, 3, 24));
+        assert!(rect.contains(&Point::new(9, 3)));
+        assert!(!rect.contains(&Point::new(10, 3)));
+
+        let other = Rect::from_corners(Point::new(8, 0), Point::new(12, 3));
+        assert_eq!(
+            rect.intersection(&other),
+            Some(Rect::from_corners(Point::new(8, 3), Point::new(9, 3)))
+        );
+        assert!(!rect.overlaps_interior(&other));
+        assert!(rect.contains_rect(&Rect::from_corners(Point::new(3, 4), Point::new(9, 5))));
+        assert!(!rect.intersects(&Rect::from_corners(Point::new(0, 0), Point::new(1, 9))));
+
+        let points = [Point::new(3, -1), Point::new(0, 4), Point::new(2, 2)];
+        assert_eq!(
+            Rect::bounding(&points),
+            Some(Rect::from_corners(Point::new(0, -1), Point::new(3, 4)))
+        );
+        assert_eq!(Rect::bounding(&[]), None);
+    }
+
+    #[test]
+    fn test_polygon_contains() {
+        let polygon = polygon();
+        assert!(polygon.contains(&Point::new(7, 1)));
+        assert!(polygon.contains(&Point::new(5, 4)));
+        assert!(polygon.contains(&Point::new(10, 6)));
+        assert!(!polygon.contains(&Point::new(5, 6)));
+        assert!(!polygon.contains(&Point::new(12, 4)));
+
+        assert!(polygon.contains_rect(&Rect::from_corners(Point::new(9, 5), Point::new(2, 3))));
+        assert!(!polygon.contains_rect(&Rect::from_corners(Point::new(2, 5), Point::new(11, 1))));
+    }
+
+    #[test]
+    fn test_polygon_measures() {
+        let polygon = polygon();
+        assert_eq!(
+            polygon.bounding_box(),
+            Some(Rect::from_corners(Point::new(2, 1), Point::new(11, 7)))
+        );
+        assert_eq!(polygon.perimeter(), 30);
+        assert_eq!(polygon.double_area(), 2 * 30);
+    }
+}
@@ -0,0 +1,304 @@
+// A rectangular grid stored row by row in a single `Vec`, shared by the days whose input is a
+// character map. Positions are `(row, col)` pairs counted from the top left.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{Error, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Up, left, right and down, in reading order.
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// All eight surrounding positions, in reading order.
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from its rows, `None` if they aren't all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a character map, one row per line, turning every character into a cell with
+    /// `cell`. A character it returns `None` for and a row of a different length are parse errors.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (row, line) in input.lines().enumerate() {
+            let mut columns = 0;
+            for (col, c) in line.chars().enumerate() {
+                let value = cell(c).ok_or_else(|| {
+                    Error::parse(row + 1, col + 1, format!("unexpected character '{}'", c))
+                })?;
+                cells.push(value);
+                columns += 1;
+            }
+
+            let expected = *width.get_or_insert(columns);
+            if columns != expected {
+                return Err(Error::parse(
+                    row + 1,
+                    columns.min(expected) + 1,
+                    format!(
+                        "the row is {} cells wide, the rows above are {}",
+                        columns, expected
+                    ),
+                ));
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.offset(row, col).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        self.offset(row, col).map(|i| &mut self.cells[i])
+    }
+
+    /// The position `(row, col)` if it is inside the grid, for callers working with signed
+    /// coordinates that may have stepped off an edge.
+    pub fn position(&self, row: isize, col: isize) -> Option<(usize, usize)> {
+        let (row, col) = (usize::try_from(row).ok()?, usize::try_from(col).ok()?);
+        self.offset(row, col).map(|_| (row, col))
+    }
+
+    fn offset(&self, row: usize, col: usize) -> Option<usize> {
+        (row < self.height && col < self.width).then(|| row * self.width + col)
+    }
+
+    /// The positions up, left, right and down of `(row, col)` that are inside the grid.
+    pub fn neighbours4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.step(row, col, &ORTHOGONAL)
+    }
+
+    /// The positions around `(row, col)`, diagonals included, that are inside the grid.
+    pub fn neighbours8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.step(row, col, &SURROUNDING)
+    }
+
+    fn step<'a>(
+        &'a self,
+        row: usize,
+        col: usize,
+        directions: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        directions
+            .iter()
+            .filter_map(move |(dr, dc)| self.position(row as isize + dr, col as isize + dc))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(col < self.width, "column {} is outside the grid", col);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    /// Every position in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+/// An empty grid, whatever the cells would be.
+impl<T> Default for Grid<T> {
+    fn default() -> Grid<T> {
+        Grid {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        match self.offset(row, col) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "({}, {}) is outside the {}x{} grid",
+                row, col, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        match self.offset(row, col) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "({}, {}) is outside the {}x{} grid",
+                row, col, self.width, self.height
+            ),
+        }
+    }
+}
+
+/// Prints every row on its own line, the cells next to each other.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456\r\n789\n", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[(1, 2)], 6);
+        assert_eq!(grid.get(2, 0), Some(&7));
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.to_string(), "123\n456\n789\n");
+
+        let empty = Grid::parse("", |c| c.to_digit(10)).unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(empty.rows().count(), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Grid::parse("12\n3x", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 2: unexpected character 'x'"
+        );
+
+        let err = Grid::parse("12\n3\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 2: the row is 1 cells wide, the rows above are 2"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside the 3x3 grid")]
+    fn test_index_out_of_bounds() {
+        let _ = digits()[(3, 0)];
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits();
+        let values = |positions: Vec<(usize, usize)>| -> Vec<u32> {
+            positions.into_iter().map(|p| grid[p]).collect()
+        };
+        assert_eq!(values(grid.neighbours4(1, 1).collect()), [2, 4, 6, 8]);
+        assert_eq!(values(grid.neighbours4(0, 0).collect()), [2, 4]);
+        assert_eq!(
+            values(grid.neighbours8(1, 1).collect()),
+            [1, 2, 3, 4, 6, 7, 8, 9]
+        );
+        assert_eq!(values(grid.neighbours8(2, 2).collect()), [5, 6, 8]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = digits();
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.rows().last().unwrap(), [7, 8, 9]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5, 8]);
+        assert_eq!(grid.positions().nth(4), Some((1, 1)));
+        assert_eq!(grid.iter().last(), Some(((2, 2), &9)));
+    }
+
+    #[test]
+    fn test_from_rows_and_map() {
+        let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+        assert_eq!(grid.map(|n| n * 10)[(1, 0)], 30);
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+
+        let mut grid = Grid::new(2, 1, '.');
+        grid[(0, 1)] = '#';
+        *grid.get_mut(0, 0).unwrap() = '#';
+        assert_eq!(grid.to_string(), "##\n");
+    }
+}
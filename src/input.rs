@@ -0,0 +1,268 @@
+// Loading puzzle inputs. Inputs come from an input directory, one `dayNN.txt` per day, another
+// file or stdin, and are cleaned up before they reach the day's parser so the parsers don't each
+// have to cope with CRLF line endings, trailing spaces and blank lines at the end of the file.
+// None of the clean ups move a character to another line or column, so a parse error still points
+// at the right place in the original file.
+//
+// Inputs aren't cached. Every command reads its input once, and `watch` only reads it again after
+// the file's modification time changed, when a cache keyed on it would miss anyway.
+
+use std::io::Read;
+use std::path::PathBuf;
+
+use crate::error::{Error, Result};
+use crate::solution::Day;
+
+pub const DEFAULT_DIR: &str = "input";
+
+/// What is cleaned up in an input before parsing it. Days whose parser relies on something being
+/// left alone (the padded rows of day 6) turn that off in `Solution::NORMALIZE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalize {
+    /// `\r\n` becomes `\n`.
+    pub line_endings: bool,
+    /// Spaces and tabs at the end of a line are dropped.
+    pub trailing_whitespace: bool,
+    /// Blank lines at the end of the input are dropped.
+    pub trailing_blank_lines: bool,
+}
+
+impl Normalize {
+    pub const ALL: Normalize = Normalize {
+        line_endings: true,
+        trailing_whitespace: true,
+        trailing_blank_lines: true,
+    };
+
+    pub const NONE: Normalize = Normalize {
+        line_endings: false,
+        trailing_whitespace: false,
+        trailing_blank_lines: false,
+    };
+}
+
+/// A puzzle input ready to parse, with where it came from and what was changed in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub source: String,
+    pub text: String,
+    normalize: Normalize,
+    changes: Vec<String>,
+}
+
+impl Input {
+    pub fn new(source: impl Into<String>, text: &str, normalize: Normalize) -> Input {
+        let (text, changes) = normalize_text(text, normalize);
+        Input {
+            source: source.into(),
+            text,
+            normalize,
+            changes,
+        }
+    }
+
+    /// What was done to the input, e.g. "converted 3 CRLF line endings", empty if nothing.
+    pub fn changes(&self) -> &[String] {
+        &self.changes
+    }
+
+    /// Adds the input's source and the normalization applied to it to an error from parsing or
+    /// solving it.
+    pub fn context(&self, error: Error) -> Error {
+        let normalization = if self.normalize == Normalize::NONE {
+            "read as is, not normalized".to_string()
+        } else if self.changes.is_empty() {
+            String::new()
+        } else {
+            format!("normalized: {}", self.changes.join(", "))
+        };
+        Error::Input {
+            source: self.source.clone(),
+            normalization,
+            error: Box::new(error),
+        }
+    }
+}
+
+/// Where inputs are read from and whether they are normalized, shared by every subcommand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loader {
+    pub dir: PathBuf,
+    /// Hand inputs to the parsers exactly as read.
+    pub raw: bool,
+}
+
+impl Default for Loader {
+    fn default() -> Loader {
+        Loader {
+            dir: PathBuf::from(DEFAULT_DIR),
+            raw: false,
+        }
+    }
+}
+
+impl Loader {
+    /// The day's input in the input directory, e.g. `input/day07.txt`.
+    pub fn day_path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{:02}.txt", day))
+    }
+
+    pub fn normalize(&self, day: &Day) -> Normalize {
+        if self.raw {
+            Normalize::NONE
+        } else {
+            day.normalize()
+        }
+    }
+
+    /// Reads the input for a day from `path`, `-` for stdin, or from the input directory.
+    pub fn load(&self, day: &Day, path: Option<&str>) -> Result<Input> {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => self.day_path(day.day),
+        };
+        let text = if path.as_os_str() == "-" {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| Error::io("<stdin>", e))?;
+            text
+        } else {
+            std::fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?
+        };
+        let source = if path.as_os_str() == "-" {
+            "<stdin>".to_string()
+        } else {
+            path.display().to_string()
+        };
+        Ok(Input::new(source, &text, self.normalize(day)))
+    }
+}
+
+fn normalize_text(text: &str, normalize: Normalize) -> (String, Vec<String>) {
+    if normalize == Normalize::NONE {
+        return (text.to_string(), Vec::new());
+    }
+
+    let mut lines: Vec<&str> = text.split('\n').collect();
+    // the empty string after the final newline isn't a line
+    let final_newline = text.ends_with('\n');
+    if final_newline {
+        lines.pop();
+    }
+
+    let (mut crlf, mut trimmed, mut dropped) = (0, 0, 0);
+    for line in &mut lines {
+        if normalize.line_endings
+            && let Some(stripped) = line.strip_suffix('\r')
+        {
+            *line = stripped;
+            crlf += 1;
+        }
+        if normalize.trailing_whitespace {
+            let stripped = line.trim_end_matches([' ', '\t']);
+            if stripped.len() != line.len() {
+                *line = stripped;
+                trimmed += 1;
+            }
+        }
+    }
+    if normalize.trailing_blank_lines {
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+            dropped += 1;
+        }
+    }
+
+    let mut normalized = lines.join("\n");
+    if final_newline && !lines.is_empty() {
+        normalized.push('\n');
+    }
+
+    let changes = [
+        (
+            crlf,
+            format!("converted {}", count(crlf, "CRLF line ending")),
+        ),
+        (
+            trimmed,
+            format!("trimmed trailing whitespace on {}", count(trimmed, "line")),
+        ),
+        (
+            dropped,
+            format!("dropped {}", count(dropped, "trailing blank line")),
+        ),
+    ]
+    .into_iter()
+    .filter(|(n, _)| *n > 0)
+    .map(|(_, change)| change)
+    .collect();
+    (normalized, changes)
+}
+
+fn count(n: usize, thing: &str) -> String {
+    if n == 1 {
+        format!("1 {}", thing)
+    } else {
+        format!("{} {}s", n, thing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let input = Input::new("t", "1,2 \r\n3,4\r\n\r\n\n", Normalize::ALL);
+        assert_eq!(input.text, "1,2\n3,4\n");
+        assert_eq!(
+            input.changes(),
+            [
+                "converted 3 CRLF line endings",
+                "trimmed trailing whitespace on 1 line",
+                "dropped 2 trailing blank lines"
+            ]
+        );
+
+        let untouched = Input::new("t", "ab\ncd", Normalize::ALL);
+        assert_eq!(untouched.text, "ab\ncd");
+        assert!(untouched.changes().is_empty());
+
+        // day 6 keeps the padding of its rows
+        let padded = Normalize {
+            trailing_whitespace: false,
+            ..Normalize::ALL
+        };
+        assert_eq!(Input::new("t", "1 \n+ \n\n", padded).text, "1 \n+ \n");
+        assert_eq!(Input::new("t", "a \r\n", Normalize::NONE).text, "a \r\n");
+    }
+
+    #[test]
+    fn test_context() {
+        let input = Input::new("input/day09.txt", "1,2\r\n\r\n", Normalize::ALL);
+        let err = input.context(Error::parse(1, 2, "expected a digit"));
+        assert_eq!(
+            err.to_string(),
+            "input/day09.txt (normalized: converted 2 CRLF line endings, dropped 1 trailing blank \
+             line): parse error at line 1, column 2: expected a digit"
+        );
+
+        let raw = Input::new("<stdin>", "1,2", Normalize::NONE);
+        assert_eq!(
+            raw.context(Error::parse(1, 1, "x")).to_string(),
+            "<stdin> (read as is, not normalized): parse error at line 1, column 1: x"
+        );
+        let clean = Input::new("<stdin>", "1,2", Normalize::ALL);
+        assert_eq!(
+            clean.context(Error::parse(1, 1, "x")).to_string(),
+            "<stdin>: parse error at line 1, column 1: x"
+        );
+        assert_eq!(
+            clean
+                .context(Error::NoSolution("x".to_string()))
+                .to_string(),
+            "<stdin>: no solution: x"
+        );
+    }
+}
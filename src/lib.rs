@@ -0,0 +1,35 @@
+// Advent of code 2025 solutions as a library. Every day exposes its parser and solvers from its
+// own module and is registered in `registry::DAYS`, the `aoc25` binary is a thin CLI over this.
+
+pub mod answers;
+pub mod bench;
+pub mod compare;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day6a;
+pub mod day6b;
+pub mod differential;
+pub mod error;
+pub mod export;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod memory;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod serve;
+pub mod solution;
+pub mod visualize;
+pub mod watch;
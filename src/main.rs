@@ -1,83 +1,380 @@
-// Advent of code 2026
-// This binary is run with the day in question as an argument --e.g., --day 1
-
-use clap::Parser;
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day6a;
-mod day6b;
+// Advent of code 2025
+// This binary is run with the day in question as an argument --e.g., --day 1. It only parses the
+// command line and hands each command to the library.
+
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use aoc25::answers::{self, KnownAnswers};
+use aoc25::error::{Error, Result};
+use aoc25::input::{self, Loader};
+use aoc25::runner::{self, Format};
+use aoc25::solution::{Day, Part};
+use aoc25::{bench, compare, differential, generate, registry, scaffold, serve, visualize, watch};
+use clap::{Parser, Subcommand};
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::filter::LevelFilter;
+
+// Counts every allocation, so runs can report what they allocated. See `aoc25::memory`.
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: aoc25::memory::Counting = aoc25::memory::Counting;
 
 #[derive(Parser, Debug)]
-#[command(name = "aoc26")]
-#[command(about = "Advent of Code 2026 Solutions", long_about = None)]
+#[command(name = "aoc25")]
+#[command(about = "Advent of Code 2025 Solutions", long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Day number to run (1-25)
+    #[arg(short, long, required_unless_present = "all")]
+    day: Option<u8>,
+
+    /// Run every registered day against its default input and print a timing summary
+    #[arg(long, conflicts_with_all = ["day", "input"])]
+    all: bool,
+
+    /// Run the days concurrently on the rayon thread pool when used with --all
+    #[arg(long, requires = "all")]
+    parallel: bool,
+
+    /// Only run one part of the puzzle, both are run by default
+    #[arg(short, long)]
+    part: Option<Part>,
+
+    /// Puzzle input file, use `-` to read from stdin [default: input/dayNN.txt]
     #[arg(short, long)]
-    day: u8,
+    input: Option<String>,
+
+    /// Check the answers against the known answers file, exits non-zero on a mismatch
+    #[arg(long)]
+    verify: bool,
+
+    /// Make --verify also fail on parts the answers file has no answer for
+    #[arg(long, requires = "verify")]
+    strict: bool,
+
+    /// Run the day against the examples from the puzzle text and compare with their answers
+    #[arg(long, conflicts_with_all = ["all", "input", "verify", "bench"])]
+    example: bool,
+
+    /// Known answers file used by --verify
+    #[arg(long, default_value = answers::DEFAULT_PATH)]
+    answers: String,
+
+    /// Benchmark the day: solve each part N times and report timing statistics
+    #[arg(long, value_name = "N", conflicts_with_all = ["all", "verify"],
+          value_parser = clap::value_parser!(u32).range(1..))]
+    bench: Option<u32>,
+
+    /// Untimed runs before benchmarking starts
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "bench")]
+    warmup: u32,
+
+    /// Solve with one of the day's named strategies instead of the default, `compare` lists them
+    #[arg(long, conflicts_with_all = ["all", "example", "watch"])]
+    strategy: Option<String>,
+
+    /// Seconds to wait for a --strategy run or benchmark before giving up on it
+    #[arg(long, default_value_t = runner::DEFAULT_TIMEOUT_SECS, requires = "strategy")]
+    timeout: u64,
+
+    /// Run the day again every time its input file changes and show how the answers and timings
+    /// moved since the previous run
+    #[arg(long, conflicts_with_all = ["all", "verify", "example", "bench", "format"])]
+    watch: bool,
+
+    /// Output format for runs and --bench
+    #[arg(long, value_enum, default_value_t = Format::Text,
+          conflicts_with_all = ["verify", "example"])]
+    format: Format,
+
+    /// Directory the days' inputs are read from, as dayNN.txt
+    #[arg(long, default_value = input::DEFAULT_DIR, global = true)]
+    input_dir: PathBuf,
+
+    /// Hand the input to the parser exactly as read, without converting CRLF line endings or
+    /// dropping trailing whitespace and blank lines
+    #[arg(long, global = true)]
+    raw: bool,
+
+    /// Log progress to stderr, repeat for more detail (-v info, -vv debug, -vvv trace).
+    /// RUST_LOG takes precedence, e.g. RUST_LOG=aoc25::day12=debug
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
 }
 
-fn main() {
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Generate the module skeleton and an empty input file for a day and register it
+    NewDay {
+        /// Day number to create (1-25)
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Generate a synthetic puzzle input for a day (days 1, 2, 3, 4, 9, 11 and 12)
+    Generate {
+        /// Day number to generate an input for
+        day: u8,
+
+        /// How big the input is, e.g. the number of rotations for day 1 [default: about the size
+        /// of the real input]
+        #[arg(long)]
+        size: Option<usize>,
+
+        /// Seed for the random generator, the same seed always gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Write the input to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Check the fast solvers against their brute-force counterparts on random inputs
+    Differential {
+        /// Checks to run, e.g. day07-paths [default: all of them]
+        checks: Vec<String>,
+
+        /// Random inputs per check
+        #[arg(long, default_value_t = 1000)]
+        cases: usize,
+
+        /// Seed for the random inputs
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Animate a day's solver step by step in the terminal (days 4, 7, 9 and 12)
+    Visualize {
+        /// Day number to visualize
+        day: u8,
+
+        /// Puzzle input file, use `-` to read from stdin [default: input/dayNN.txt]
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// The region to pack on day 12, counting from 0
+        #[arg(long, default_value_t = 0)]
+        region: usize,
+
+        /// Milliseconds between frames while playing
+        #[arg(long, default_value_t = 100)]
+        delay: u64,
+
+        /// Write a picture to this file instead of playing, as .ppm, .png or .svg (day 9 only svg)
+        #[arg(long)]
+        export: Option<PathBuf>,
+    },
+    /// Run every strategy of a day on the same input, check they agree and compare their timings
+    Compare {
+        /// Day number to compare the strategies of
+        day: u8,
+
+        /// Only compare one part, both are compared by default
+        #[arg(short, long)]
+        part: Option<Part>,
+
+        /// Puzzle input file, use `-` to read from stdin [default: input/dayNN.txt]
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// Seconds to wait for a strategy before giving up on it
+        #[arg(long, default_value_t = runner::DEFAULT_TIMEOUT_SECS)]
+        timeout: u64,
+    },
+    /// Serve the solvers as a JSON API on localhost: POST the input to /days/{day}/parts/{part}
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = serve::DEFAULT_PORT)]
+        port: u16,
+
+        /// Seconds to wait for a solver before answering the request with an error
+        #[arg(long, default_value_t = runner::DEFAULT_TIMEOUT_SECS)]
+        timeout: u64,
+    },
+}
+
+fn main() -> ExitCode {
     let args = Args::parse();
+    init_logging(args.verbose);
 
-    match args.day {
-        1 => {
-            day01::solve_problem_1("input/day01.txt").unwrap();
-            day01::solve_problem_2("input/day01.txt").unwrap();
-        }
-        2 => {
-            day02::solve_problem_1("input/day02.txt").unwrap();
-            day02::solve_problem_2("input/day02.txt").unwrap();
-        }
-        3 => {
-            day03::solve_problem_1("input/day03.txt").unwrap();
-            day03::solve_problem_2("input/day03.txt").unwrap();
+    match run(args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
         }
-        4 => {
-            day04::solve_problem_1("input/day04.txt").unwrap();
-            day04::solve_problem_2("input/day04.txt").unwrap();
+    }
+}
+
+// Only warnings are logged by default, so a normal run prints just the answers.
+fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    let filter = EnvFilter::builder()
+        .with_default_directive(level.into())
+        .from_env_lossy();
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .with_target(false)
+        .compact()
+        .init();
+}
+
+fn run(args: Args) -> Result<ExitCode> {
+    let loader = Loader {
+        dir: args.input_dir,
+        raw: args.raw,
+    };
+
+    match args.command {
+        Some(Command::NewDay { day }) => {
+            for path in scaffold::new_day(day)? {
+                println!("wrote {}", path.display());
+            }
+            return Ok(ExitCode::SUCCESS);
         }
-        5 => {
-            day05::solve_problem_1("input/day05.txt").unwrap();
-            day05::solve_problem_2("input/day05.txt").unwrap();
+        Some(Command::Generate {
+            day,
+            size,
+            seed,
+            output,
+        }) => {
+            let input = generate::generate(day, size, seed)?;
+            match output {
+                Some(path) => std::fs::write(&path, input).map_err(|e| Error::io(path, e))?,
+                None => print!("{}", input),
+            }
+            return Ok(ExitCode::SUCCESS);
         }
-        6 => {
-            day6a::solve_problem_1("input/day06.txt").unwrap();
-            day6b::solve_problem_2("input/day06.txt").unwrap();
+        Some(Command::Differential {
+            checks,
+            cases,
+            seed,
+        }) => return Ok(exit_code(differential::run_checks(&checks, cases, seed)?)),
+        Some(Command::Visualize {
+            day,
+            input,
+            region,
+            delay,
+            export,
+        }) => {
+            let registered = find_day(day)?;
+            let input = loader.load(registered, input.as_deref())?;
+            let delay = Duration::from_millis(delay);
+            visualize::visualize(day, &input, region, delay, export.as_deref())?;
+            return Ok(ExitCode::SUCCESS);
         }
-        7 => {
-            day07::solve_problem_1("input/day07.txt").unwrap();
-            day07::solve_problem_2("input/day07.txt").unwrap();
+        Some(Command::Compare {
+            day,
+            part,
+            input,
+            timeout,
+        }) => {
+            let day = find_day(day)?;
+            let input = loader.load(day, input.as_deref())?;
+            let timeout = Duration::from_secs(timeout);
+            let comparisons = compare::compare(day, &input, &parts(part), timeout)?;
+            return Ok(exit_code(compare::print(day, &comparisons)));
         }
-        8 => {
-            day08::solve_problem_1("input/day08.txt").unwrap();
-            day08::solve_problem_2("input/day08.txt").unwrap();
+        Some(Command::Serve { port, timeout }) => {
+            serve::serve(port, Duration::from_secs(timeout))?;
+            return Ok(ExitCode::SUCCESS);
         }
-        9 => {
-            day09::solve_problem_1("input/day09.txt").unwrap();
-            day09::solve_problem_2("input/day09.txt").unwrap();
+        None => {}
+    }
+
+    let parts = parts(args.part);
+    let start = Instant::now();
+    let (runs, input_path) = if args.all {
+        (runner::run_all(&loader, &parts, args.parallel), None)
+    } else {
+        let day = find_day(args.day.expect("clap requires --day without --all"))?;
+        if args.example {
+            return Ok(exit_code(runner::print_examples(day, &parts)));
         }
-        10 => {
-            day10::solve_problem_1_iterative_deepening("input/day10.txt").unwrap();
-            day10::solve_problem_2_linear_programming("input/day10.txt").unwrap();
+
+        let path = args
+            .input
+            .unwrap_or_else(|| loader.day_path(day.day).display().to_string());
+        let strategy = args.strategy.as_deref();
+        let timeout = Duration::from_secs(args.timeout);
+        if let Some(name) = strategy {
+            runner::check_strategy(day, &parts, name)?;
         }
-        11 => {
-            day11::solve_problem_1("input/day11.txt").unwrap();
-            day11::solve_problem_2("input/day11.txt").unwrap();
+
+        if args.watch {
+            watch::watch(day, &loader, &path, &parts)?;
+            return Ok(ExitCode::SUCCESS);
         }
-        12 => {
-            day12::solve_problem_1("input/day12.txt").unwrap();
-            day12::solve_problem_2("input/day12.txt").unwrap();
+
+        if let Some(iterations) = args.bench {
+            let input = loader.load(day, Some(&path))?;
+            let reports = bench::bench_parts(
+                day,
+                &input,
+                &parts,
+                strategy,
+                timeout,
+                args.warmup as usize,
+                iterations as usize,
+            )?;
+            bench::print(&reports, args.format);
+            return Ok(ExitCode::SUCCESS);
         }
-        _ => println!("Day {} not implemented yet", args.day),
+
+        let run = loader
+            .load(day, Some(&path))
+            .and_then(|input| match strategy {
+                Some(name) => runner::run_strategy(day, &input, &parts, name, timeout),
+                None => runner::run_input(day, &input, &parts, None),
+            });
+        (vec![(day.day, run)], Some(path))
+    };
+
+    if args.verify {
+        let known = KnownAnswers::load(&args.answers)?;
+        return Ok(exit_code(runner::print_verification(
+            &runs,
+            &known,
+            args.strict,
+        )));
+    }
+    if args.format == Format::Json {
+        let ok = runner::print_json(&runs, &loader, input_path.as_deref(), &parts);
+        return Ok(exit_code(ok));
+    }
+    if args.all {
+        runner::print_summary(&runs, start.elapsed());
+        Ok(ExitCode::SUCCESS)
+    } else {
+        let (_, run) = runs.into_iter().next().expect("a single day was run");
+        Ok(exit_code(runner::print_run(&run?)))
+    }
+}
+
+fn find_day(day: u8) -> Result<&'static Day> {
+    registry::find(day)
+        .ok_or_else(|| Error::InvalidInput(format!("day {} is not implemented yet", day)))
+}
+
+fn parts(part: Option<Part>) -> Vec<Part> {
+    part.map_or(Part::BOTH.to_vec(), |part| vec![part])
+}
+
+fn exit_code(success: bool) -> ExitCode {
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
     }
 }
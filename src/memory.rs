@@ -0,0 +1,197 @@
+// Allocation accounting for the runner. With the `alloc-stats` feature the binary installs
+// `Counting` as its global allocator, which counts every allocation, so a run can report how much
+// it allocated and how much was live at once on top of how long it took:
+//
+//   cargo run --release --features alloc-stats -- --all
+//
+// The counters are global, the figures for a step also include whatever other threads allocate
+// meanwhile. That is what we want for a day that spreads itself over rayon, but days run with
+// --parallel get each other's allocations mixed in. Counting makes every allocation a little
+// slower, timings taken with the feature are not comparable with ones taken without it. Without
+// the feature, or in a program that doesn't install `Counting`, nothing is counted and `measure`
+// reports nothing.
+
+use std::fmt;
+
+use serde::Serialize;
+
+/// What a step allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct AllocStats {
+    /// Bytes allocated, including the ones freed again. A reallocation that grows a block adds
+    /// what it grew by.
+    pub bytes: u64,
+    /// The most bytes live at any one time, above what was live when the step started.
+    pub peak_bytes: u64,
+    /// Number of allocations, a reallocation counts as one.
+    pub count: u64,
+}
+
+/// Runs `f`, returning what it allocated when `Counting` is the global allocator.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    #[cfg(feature = "alloc-stats")]
+    {
+        counting::measure(f)
+    }
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        (f(), None)
+    }
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} in {} allocations, peak {}",
+            Bytes(self.bytes),
+            self.count,
+            Bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// A byte count in binary units, e.g. `1.50 MiB`.
+pub struct Bytes(pub u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return f.pad(&format!("{} B", self.0));
+        }
+        let mut value = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        f.pad(&format!("{:.2} {}", value, UNITS[unit]))
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+pub use counting::Counting;
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+    use super::AllocStats;
+
+    static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+    static COUNT: AtomicU64 = AtomicU64::new(0);
+    static LIVE: AtomicU64 = AtomicU64::new(0);
+    static PEAK: AtomicU64 = AtomicU64::new(0);
+
+    /// The system allocator, counting what goes through it. Install it with
+    /// `#[global_allocator] static ALLOCATOR: Counting = Counting;`.
+    pub struct Counting;
+
+    fn allocated(size: usize) {
+        let size = size as u64;
+        ALLOCATED.fetch_add(size, Relaxed);
+        COUNT.fetch_add(1, Relaxed);
+        let live = LIVE.fetch_add(size, Relaxed) + size;
+        PEAK.fetch_max(live, Relaxed);
+    }
+
+    fn freed(size: usize) {
+        LIVE.fetch_sub(size as u64, Relaxed);
+    }
+
+    // Only what a block grew by is new, a shrunk block frees its tail.
+    fn reallocated(old_size: usize, new_size: usize) {
+        if new_size > old_size {
+            allocated(new_size - old_size);
+        } else {
+            COUNT.fetch_add(1, Relaxed);
+            freed(old_size - new_size);
+        }
+    }
+
+    // SAFETY: every call is passed straight on to the system allocator, the counters are only
+    // updated around it.
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            freed(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new.is_null() {
+                reallocated(layout.size(), new_size);
+            }
+            new
+        }
+    }
+
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+        // nothing has ever been counted when `Counting` isn't the global allocator
+        if COUNT.load(Relaxed) == 0 {
+            return (f(), None);
+        }
+        let live = LIVE.load(Relaxed);
+        PEAK.store(live, Relaxed);
+        let (bytes, count) = (ALLOCATED.load(Relaxed), COUNT.load(Relaxed));
+
+        let value = f();
+
+        let stats = AllocStats {
+            bytes: ALLOCATED.load(Relaxed) - bytes,
+            peak_bytes: PEAK.load(Relaxed).saturating_sub(live),
+            count: COUNT.load(Relaxed) - count,
+        };
+        (value, Some(stats))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "alloc-stats")]
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(Bytes(512).to_string(), "512 B");
+        assert_eq!(Bytes(1536).to_string(), "1.50 KiB");
+        assert_eq!(Bytes(5 * 1024 * 1024).to_string(), "5.00 MiB");
+        assert_eq!(format!("{:>10}", Bytes(2048)), "  2.00 KiB");
+    }
+
+    #[test]
+    fn test_measure() {
+        let (len, stats) = measure(|| vec![0u8; 4096].len());
+        assert_eq!(len, 4096);
+
+        if cfg!(feature = "alloc-stats") {
+            // the other tests allocate on their own threads at the same time, only lower bounds hold
+            let stats = stats.unwrap();
+            assert!(stats.bytes >= 4096, "{:?}", stats);
+            assert!(stats.count >= 1, "{:?}", stats);
+        } else {
+            assert_eq!(stats, None);
+        }
+    }
+}
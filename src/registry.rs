@@ -0,0 +1,41 @@
+use crate::solution::Day;
+
+/// Every solved day, in order. New days register here, `aoc25 new-day <N>` adds the entry.
+pub const DAYS: &[Day] = &[
+    Day::new::<crate::day01::Day01>(1),
+    Day::new::<crate::day02::Day02>(2),
+    Day::new::<crate::day03::Day03>(3),
+    Day::new::<crate::day04::Day04>(4),
+    Day::new::<crate::day05::Day05>(5),
+    Day::new::<crate::day06::Day06>(6),
+    Day::new::<crate::day07::Day07>(7),
+    Day::new::<crate::day08::Day08>(8),
+    Day::new::<crate::day09::Day09>(9),
+    Day::new::<crate::day10::Day10>(10),
+    Day::new::<crate::day11::Day11>(11),
+    Day::new::<crate::day12::Day12>(12),
+];
+
+pub fn find(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_unique_and_ordered() {
+        assert!(DAYS.windows(2).all(|w| w[0].day < w[1].day));
+        assert_eq!(DAYS[0].day, 1);
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(
+            find(6).map(|d| d.input_path()),
+            Some("input/day06.txt".to_string())
+        );
+        assert!(find(25).is_none());
+    }
+}
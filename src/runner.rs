@@ -0,0 +1,589 @@
+use std::any::Any;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::answers::{KnownAnswers, Verdict};
+use crate::error::{Error, Result};
+use crate::input::{Input, Loader};
+use crate::memory::{self, AllocStats, Bytes};
+use crate::registry;
+use crate::solution::{Answer, Day, Part};
+
+/// How long a run with a named strategy, or a strategy in `compare`, is given before it is given
+/// up on. Some strategies are exhaustive searches that won't finish on a real input.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// How runs and benchmarks are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+pub struct PartRun {
+    pub part: Part,
+    /// A part that fails to solve doesn't stop the other part from being solved.
+    pub answer: Result<Answer>,
+    pub solve_time: Duration,
+    pub solve_alloc: Option<AllocStats>,
+}
+
+/// The outcome of running a day: the time spent parsing the input and each part's answer along
+/// with the time spent solving it. The allocations are only counted with the `alloc-stats`
+/// feature.
+pub struct DayRun {
+    pub day: u8,
+    pub parse_time: Duration,
+    pub parse_alloc: Option<AllocStats>,
+    pub parts: Vec<PartRun>,
+}
+
+impl DayRun {
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|p| p.solve_time).sum::<Duration>()
+    }
+
+    /// Whether every part was solved.
+    pub fn solved(&self) -> bool {
+        self.parts.iter().all(|p| p.answer.is_ok())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    /// The input didn't parse, so no part was solved.
+    ParseError,
+    /// The part's solver failed, the other part may still have been solved.
+    SolveError,
+    /// Anything else, such as an input that couldn't be read.
+    Error,
+}
+
+/// The machine-readable outcome of one part of a day. A day that fails to read or parse its
+/// input reports the error against every part that was requested, a part that fails to solve only
+/// against that part.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solve_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_alloc: Option<AllocStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solve_alloc: Option<AllocStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Report {
+    pub fn from_run(day: u8, input: &str, parts: &[Part], run: &Result<DayRun>) -> Vec<Report> {
+        match run {
+            Ok(run) => run
+                .parts
+                .iter()
+                .map(|part| Report {
+                    day,
+                    part: part.part,
+                    input: input.to_string(),
+                    status: match part.answer {
+                        Ok(_) => Status::Ok,
+                        Err(_) => Status::SolveError,
+                    },
+                    answer: part.answer.as_ref().ok().cloned(),
+                    parse_ns: Some(run.parse_time.as_nanos() as u64),
+                    solve_ns: Some(part.solve_time.as_nanos() as u64),
+                    parse_alloc: run.parse_alloc,
+                    solve_alloc: part.solve_alloc,
+                    error: part.answer.as_ref().err().map(|e| e.to_string()),
+                })
+                .collect(),
+            Err(e) => parts
+                .iter()
+                .map(|&part| Report {
+                    day,
+                    part,
+                    input: input.to_string(),
+                    status: if e.is_parse() {
+                        Status::ParseError
+                    } else {
+                        Status::Error
+                    },
+                    answer: None,
+                    parse_ns: None,
+                    solve_ns: None,
+                    parse_alloc: None,
+                    solve_alloc: None,
+                    error: Some(e.to_string()),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Parses the input once and then solves the requested parts against it. Only a failure to parse
+/// fails the run, each part keeps its own answer or error.
+pub fn run(day: &Day, input: &str, parts: &[Part]) -> Result<DayRun> {
+    run_with(day, input, parts, None)
+}
+
+/// `run` with the named strategy for the parts that have one by that name, the other parts use
+/// their default. It is an error if none of the requested parts has it.
+pub fn run_with(day: &Day, input: &str, parts: &[Part], strategy: Option<&str>) -> Result<DayRun> {
+    if let Some(name) = strategy {
+        check_strategy(day, parts, name)?;
+    }
+    let _span = tracing::info_span!("day", day = day.day).entered();
+
+    let ((parsed, parse_time), parse_alloc) = memory::measure(|| {
+        let start = Instant::now();
+        (day.parse(input), start.elapsed())
+    });
+    let parsed = parsed?;
+    tracing::debug!(elapsed = ?parse_time, alloc = ?parse_alloc, "parsed the input");
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let _span = tracing::info_span!("part", part = %part).entered();
+            let ((answer, solve_time), solve_alloc) = memory::measure(|| {
+                let start = Instant::now();
+                (solve(day, parsed.as_ref(), part, strategy), start.elapsed())
+            });
+            match &answer {
+                Ok(answer) => {
+                    tracing::info!(%answer, elapsed = ?solve_time, alloc = ?solve_alloc, "solved")
+                }
+                Err(e) => tracing::info!(error = %e, elapsed = ?solve_time, "failed"),
+            }
+            PartRun {
+                part,
+                answer,
+                solve_time,
+                solve_alloc,
+            }
+        })
+        .collect();
+
+    Ok(DayRun {
+        day: day.day,
+        parse_time,
+        parse_alloc,
+        parts,
+    })
+}
+
+/// `run_with` on a loaded input, the errors say which file it was and how it was normalized.
+pub fn run_input(
+    day: &Day,
+    input: &Input,
+    parts: &[Part],
+    strategy: Option<&str>,
+) -> Result<DayRun> {
+    let run = run_with(day, &input.text, parts, strategy).map_err(|e| input.context(e))?;
+    let parts = run
+        .parts
+        .into_iter()
+        .map(|part| PartRun {
+            answer: part.answer.map_err(|e| input.context(e)),
+            ..part
+        })
+        .collect();
+    Ok(DayRun { parts, ..run })
+}
+
+/// `run_input` with a named strategy, given up on after `timeout`. The strategies a day keeps next
+/// to its default include the exhaustive searches it replaced, which can run for hours on a real
+/// input.
+pub fn run_strategy(
+    day: &'static Day,
+    input: &Input,
+    parts: &[Part],
+    strategy: &str,
+    timeout: Duration,
+) -> Result<DayRun> {
+    let (input, parts, name) = (input.clone(), parts.to_vec(), strategy.to_string());
+    with_strategy_timeout(strategy, timeout, move || {
+        run_input(day, &input, &parts, Some(&name))
+    })
+}
+
+/// `with_timeout` for a named strategy picked on the command line, the error says how to give it
+/// longer.
+pub fn with_strategy_timeout<T: Send + 'static>(
+    strategy: &str,
+    timeout: Duration,
+    f: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T> {
+    with_timeout(&format!("the {} strategy", strategy), timeout, f).map_err(|e| match e {
+        Error::TimedOut(message) => {
+            Error::TimedOut(format!("{}, --timeout gives it longer", message))
+        }
+        e => e,
+    })
+}
+
+/// Runs `f` on its own thread and gives up on it after `timeout`, `what` names it in the errors.
+/// A run that is given up on keeps its thread busy until the process exits.
+pub fn with_timeout<T: Send + 'static>(
+    what: &str,
+    timeout: Duration,
+    f: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        // nobody is listening any more if it timed out
+        let _ = sender.send(f());
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(Error::TimedOut(format!(
+            "gave up on {} after {:?}",
+            what, timeout
+        ))),
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Err(Error::InvalidInput(format!("{} panicked", what)))
+        }
+    }
+}
+
+/// Solves one part with the named strategy if it has one, with its default otherwise.
+pub fn solve(day: &Day, input: &dyn Any, part: Part, strategy: Option<&str>) -> Result<Answer> {
+    strategy
+        .and_then(|name| day.solve_with(input, part, name))
+        .unwrap_or_else(|| day.solve(input, part))
+}
+
+pub fn check_strategy(day: &Day, parts: &[Part], name: &str) -> Result<()> {
+    if parts
+        .iter()
+        .any(|&part| day.strategies(part).contains(&name))
+    {
+        return Ok(());
+    }
+    let known = parts
+        .iter()
+        .map(|&part| format!("part {}: {}", part, day.strategies(part).join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ");
+    Err(Error::InvalidInput(format!(
+        "day {} has no strategy {} ({})",
+        day.day, name, known
+    )))
+}
+
+/// Runs every registered day against its input in the loader's directory, optionally spreading
+/// the days over the rayon thread pool.
+pub fn run_all(loader: &Loader, parts: &[Part], parallel: bool) -> Vec<(u8, Result<DayRun>)> {
+    let run_day = |day: &Day| -> (u8, Result<DayRun>) {
+        let run = loader
+            .load(day, None)
+            .and_then(|input| run_input(day, &input, parts, None));
+        (day.day, run)
+    };
+
+    if parallel {
+        registry::DAYS.par_iter().map(run_day).collect()
+    } else {
+        registry::DAYS.iter().map(run_day).collect()
+    }
+}
+
+/// Prints the answers of a single day, with what each step allocated when that is counted.
+/// Returns whether every part was solved.
+pub fn print_run(run: &DayRun) -> bool {
+    if let Some(alloc) = run.parse_alloc {
+        println!("parse: {}", alloc);
+    }
+    for part in &run.parts {
+        match (&part.answer, part.solve_alloc) {
+            (Ok(answer), Some(alloc)) => {
+                println!("problem {}: result = {} ({})", part.part, answer, alloc)
+            }
+            (Ok(answer), None) => println!("problem {}: result = {}", part.part, answer),
+            (Err(e), _) => println!("problem {}: error: {}", part.part, e),
+        }
+    }
+    run.solved()
+}
+
+/// Prints a table of every day's answers and timings, as run by `run_all`.
+pub fn print_summary(runs: &[(u8, Result<DayRun>)], wall_time: Duration) {
+    // the allocation columns are only there when the counting allocator is built in
+    let counted = runs
+        .iter()
+        .any(|(_, run)| matches!(run, Ok(run) if run.parse_alloc.is_some()));
+    println!(
+        "{:>3}  {:>4}  {:<20}  {:>10}  {:>10}{}",
+        "day",
+        "part",
+        "answer",
+        "parse",
+        "solve",
+        if counted {
+            format!("  {:>10}  {:>10}  {:>10}", "allocated", "peak", "allocs")
+        } else {
+            String::new()
+        }
+    );
+    let mut total = Duration::ZERO;
+    for (day, run) in runs {
+        match run {
+            Ok(run) => {
+                total += run.total_time();
+                for part in &run.parts {
+                    let answer = match &part.answer {
+                        Ok(answer) => answer,
+                        Err(e) => {
+                            println!("{:>3}  {:>4}  error: {}", run.day, part.part, e);
+                            continue;
+                        }
+                    };
+                    // what solving the part allocated, --format json has the parse's as well
+                    let alloc = part
+                        .solve_alloc
+                        .map(|alloc| {
+                            format!(
+                                "  {:>10}  {:>10}  {:>10}",
+                                Bytes(alloc.bytes),
+                                Bytes(alloc.peak_bytes),
+                                alloc.count
+                            )
+                        })
+                        .unwrap_or_default();
+                    println!(
+                        "{:>3}  {:>4}  {:<20}  {:>10}  {:>10}{}",
+                        run.day,
+                        part.part,
+                        answer.to_string(),
+                        format!("{:.2?}", run.parse_time),
+                        format!("{:.2?}", part.solve_time),
+                        alloc
+                    );
+                }
+            }
+            Err(e) => println!("{:>3}  {:>4}  error: {}", day, "-", e),
+        }
+    }
+    println!(
+        "total: {:.2?} of solver time, {:.2?} wall clock",
+        total, wall_time
+    );
+}
+
+/// Prints a report per day and part, returns whether every part was solved. Days run with
+/// `run_all` read their input from the loader's directory, `input_path` is the input of a single
+/// day.
+pub fn print_json(
+    runs: &[(u8, Result<DayRun>)],
+    loader: &Loader,
+    input_path: Option<&str>,
+    parts: &[Part],
+) -> bool {
+    let reports: Vec<Report> = runs
+        .iter()
+        .flat_map(|(day, run)| {
+            let path = match input_path {
+                Some(path) => path.to_string(),
+                None => loader.day_path(*day).display().to_string(),
+            };
+            Report::from_run(*day, &path, parts, run)
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+
+    reports.iter().all(|r| r.status == Status::Ok)
+}
+
+/// Prints each answer's verdict against the known answers, returns false when an answer differs,
+/// a day failed to run or no answer was known at all. `strict` also fails on every unknown answer.
+pub fn print_verification(
+    runs: &[(u8, Result<DayRun>)],
+    known: &KnownAnswers,
+    strict: bool,
+) -> bool {
+    let mut passed = true;
+    let mut checked = 0;
+    for (day, run) in runs {
+        match run {
+            Ok(run) => {
+                for part in &run.parts {
+                    let answer = match &part.answer {
+                        Ok(answer) => answer,
+                        Err(e) => {
+                            passed = false;
+                            println!("day {:>2} part {}: ERROR ({})", run.day, part.part, e);
+                            continue;
+                        }
+                    };
+                    let verdict = known.check(run.day, part.part, answer);
+                    match &verdict {
+                        Verdict::Fail { expected } => {
+                            passed = false;
+                            println!(
+                                "day {:>2} part {}: {} (expected {}, got {})",
+                                run.day, part.part, verdict, expected, answer
+                            );
+                        }
+                        _ => println!(
+                            "day {:>2} part {}: {} ({})",
+                            run.day, part.part, verdict, answer
+                        ),
+                    }
+                    match verdict {
+                        Verdict::Unknown if strict => passed = false,
+                        Verdict::Unknown => {}
+                        _ => checked += 1,
+                    }
+                }
+            }
+            Err(e) => {
+                passed = false;
+                println!("day {:>2}: ERROR ({})", day, e);
+            }
+        }
+    }
+    if checked == 0 {
+        passed = false;
+        println!("no answer was checked, the answers file has none for these days and parts");
+    }
+    passed
+}
+
+/// Runs the day against the examples from the puzzle text and prints a verdict per example and
+/// part, returns whether they all passed.
+pub fn print_examples(day: &Day, parts: &[Part]) -> bool {
+    if day.examples().is_empty() {
+        println!("Day {} has no examples", day.day);
+        return false;
+    }
+
+    let mut passed = true;
+    for (i, example) in day.examples().iter().enumerate() {
+        // only run the parts the example has an answer for, the other part may not even apply
+        let parts: Vec<Part> = parts
+            .iter()
+            .copied()
+            .filter(|&part| example.expected(part).is_some())
+            .collect();
+        if parts.is_empty() {
+            continue;
+        }
+
+        match run(day, example.input, &parts) {
+            Ok(run) => {
+                for part in &run.parts {
+                    let expected = example.expected(part.part).expect("filtered above");
+                    let answer = match &part.answer {
+                        Ok(answer) => answer,
+                        Err(e) => {
+                            passed = false;
+                            println!("example {} part {}: ERROR ({})", i + 1, part.part, e);
+                            continue;
+                        }
+                    };
+                    let verdict = if expected == answer {
+                        Verdict::Pass
+                    } else {
+                        passed = false;
+                        Verdict::Fail {
+                            expected: expected.clone(),
+                        }
+                    };
+                    println!(
+                        "example {} part {}: {} (expected {}, got {})",
+                        i + 1,
+                        part.part,
+                        verdict,
+                        expected,
+                        answer
+                    );
+                }
+            }
+            Err(e) => {
+                passed = false;
+                println!("example {}: ERROR ({})", i + 1, e);
+            }
+        }
+    }
+    passed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_from_error() {
+        let run = Err(Error::InvalidInput("empty".to_string()));
+        let reports = Report::from_run(3, "input/day03.txt", &Part::BOTH, &run);
+
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|r| r.status == Status::Error));
+        assert_eq!(
+            serde_json::to_value(&reports[1]).unwrap(),
+            serde_json::json!({
+                "day": 3,
+                "part": 2,
+                "input": "input/day03.txt",
+                "status": "error",
+                "error": "invalid input: empty",
+            })
+        );
+    }
+
+    #[test]
+    fn test_with_timeout() {
+        let quick = with_timeout("quick", Duration::from_secs(60), || Ok(42));
+        assert_eq!(quick.unwrap(), 42);
+
+        let slow = with_strategy_timeout("slow", Duration::from_millis(10), || {
+            std::thread::sleep(Duration::from_secs(1));
+            Ok(())
+        });
+        assert_eq!(
+            slow.unwrap_err().to_string(),
+            "timed out: gave up on the slow strategy after 10ms, --timeout gives it longer"
+        );
+    }
+
+    #[test]
+    fn test_report_per_part() {
+        // day 11 solves part 1 but has no 'svr' device for part 2
+        let day = registry::find(11).unwrap();
+        let run = run(day, "you: out", &Part::BOTH);
+        let reports = Report::from_run(11, "t", &Part::BOTH, &run);
+        assert_eq!(reports[0].status, Status::Ok);
+        assert_eq!(reports[0].answer, Some(Answer::Int(1)));
+        assert_eq!(reports[1].status, Status::SolveError);
+        assert_eq!(
+            reports[1].error.as_deref(),
+            Some("invalid input: the device 'svr' is missing")
+        );
+
+        let run = run_input(
+            day,
+            &Input::new("t", "you out", day.normalize()),
+            &Part::BOTH,
+            None,
+        );
+        let reports = Report::from_run(11, "t", &Part::BOTH, &run);
+        assert!(reports.iter().all(|r| r.status == Status::ParseError));
+        assert_eq!(
+            serde_json::to_value(&reports[0]).unwrap()["status"],
+            "parse_error"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+// Generates the boilerplate for a new day: the module skeleton, an empty input file, a fuzz target
+// for its parser, and the `pub mod` and registry lines. Paths are relative to the repository root,
+// like the inputs.
+
+use std::path::PathBuf;
+
+use crate::error::{Error, Result};
+
+const LIB_PATH: &str = "src/lib.rs";
+const REGISTRY_PATH: &str = "src/registry.rs";
+const FUZZ_MANIFEST_PATH: &str = "fuzz/Cargo.toml";
+
+// `DayNN` is replaced with the zero padded day
+const TEMPLATE: &str = r#"use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::solution::{Example, Solution};
+
+pub struct DayNN;
+
+// paste the example from the puzzle text here
+const EXAMPLE: &str = "\
+";
+
+impl Solution for DayNN {
+    type Input = ProblemData;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    // fill in the answers from the puzzle text to check them with --example
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: None,
+        part2: None,
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(_data: &Self::Input) -> Result<u64> {
+        Err(Error::NoSolution("part 1 is not solved yet".to_string()))
+    }
+
+    fn part2(_data: &Self::Input) -> Result<u64> {
+        Err(Error::NoSolution("part 2 is not solved yet".to_string()))
+    }
+}
+
+pub struct ProblemData {
+    pub lines: Vec<String>,
+}
+
+impl FromStr for ProblemData {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(ProblemData {
+            lines: s.lines().map(|line| line.to_string()).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_problem_1() {
+        let data: ProblemData = EXAMPLE.parse().unwrap();
+        // TODO assert results
+        assert!(DayNN::part1(&data).is_err());
+    }
+}
+"#;
+
+// `DayNN` and `dayNN` are replaced with the zero padded day
+const FUZZ_TARGET: &str = r#"#![no_main]
+
+use aoc25::dayNN::DayNN;
+use aoc25::solution::Solution;
+use libfuzzer_sys::fuzz_target;
+
+// malformed input must come back as an error, never as a panic or a hang
+fuzz_target!(|input: &str| {
+    let _ = DayNN::parse(input);
+});
+"#;
+
+/// Creates `src/dayNN.rs`, `input/dayNN.txt` and the fuzz target, and registers the day. Returns the files that
+/// were written. An existing input file is left alone, an existing module is an error.
+pub fn new_day(day: u8) -> Result<Vec<PathBuf>> {
+    let module_path = PathBuf::from(format!("src/day{:02}.rs", day));
+    if module_path.exists() {
+        return Err(Error::InvalidInput(format!(
+            "{} already exists",
+            module_path.display()
+        )));
+    }
+
+    let lib = read(LIB_PATH)?;
+    let registry = read(REGISTRY_PATH)?;
+    let fuzz_manifest = read(FUZZ_MANIFEST_PATH)?;
+    let lib = add_module(&lib, day)?;
+    let registry = register_day(&registry, day)?;
+    let fuzz_manifest = add_fuzz_target(&fuzz_manifest, day);
+
+    let mut written = Vec::new();
+    write(&module_path, &module_source(day))?;
+    written.push(module_path);
+
+    let input_path = PathBuf::from(format!("input/day{:02}.txt", day));
+    if !input_path.exists() {
+        write(&input_path, "")?;
+        written.push(input_path);
+    }
+
+    let fuzz_target_path = PathBuf::from(format!("fuzz/fuzz_targets/day{:02}.rs", day));
+    write(&fuzz_target_path, &fuzz_target_source(day))?;
+    written.push(fuzz_target_path);
+
+    write(LIB_PATH, &lib)?;
+    written.push(LIB_PATH.into());
+    write(REGISTRY_PATH, &registry)?;
+    written.push(REGISTRY_PATH.into());
+    write(FUZZ_MANIFEST_PATH, &fuzz_manifest)?;
+    written.push(FUZZ_MANIFEST_PATH.into());
+
+    Ok(written)
+}
+
+pub fn module_source(day: u8) -> String {
+    TEMPLATE.replace("DayNN", &format!("Day{:02}", day))
+}
+
+pub fn fuzz_target_source(day: u8) -> String {
+    FUZZ_TARGET
+        .replace("DayNN", &format!("Day{:02}", day))
+        .replace("dayNN", &format!("day{:02}", day))
+}
+
+/// Appends the `[[bin]]` section for the day's fuzz target to the fuzz crate's manifest.
+fn add_fuzz_target(manifest: &str, day: u8) -> String {
+    let name = format!("day{:02}", day);
+    format!(
+        "{}\n\n[[bin]]\nname = \"{}\"\npath = \"fuzz_targets/{}.rs\"\ntest = false\ndoc = false\nbench = false\n",
+        manifest.trim_end(),
+        name,
+        name
+    )
+}
+
+/// Inserts `pub mod dayNN;` among the other day modules, keeping them in order.
+fn add_module(lib: &str, day: u8) -> Result<String> {
+    let line = format!("pub mod day{:02};", day);
+    insert_in_order(lib, &line, |l| {
+        l.strip_prefix("pub mod day")?
+            .strip_suffix(';')?
+            .parse()
+            .ok()
+    })
+    .ok_or_else(|| Error::InvalidInput(format!("day {} is already a module", day)))
+}
+
+/// Inserts the `Day::new` entry for the day into `DAYS`, keeping the days in order.
+fn register_day(registry: &str, day: u8) -> Result<String> {
+    let line = format!(
+        "    Day::new::<crate::day{:02}::Day{:02}>({}),",
+        day, day, day
+    );
+    insert_in_order(registry, &line, |l| {
+        l.trim()
+            .strip_prefix("Day::new::<")?
+            .split_once(">(")?
+            .1
+            .strip_suffix("),")?
+            .parse()
+            .ok()
+    })
+    .ok_or_else(|| Error::InvalidInput(format!("day {} is already registered", day)))
+}
+
+/// Inserts `line` before the first line whose day (as read by `day_of`) is greater than the new
+/// one, or after the last line that has a day. Returns `None` if the day is already present.
+fn insert_in_order(
+    source: &str,
+    line: &str,
+    day_of: impl Fn(&str) -> Option<u8>,
+) -> Option<String> {
+    let new_day = day_of(line.trim()).expect("the inserted line has a day");
+    let lines: Vec<&str> = source.lines().collect();
+
+    let mut insert_at = None;
+    for (i, l) in lines.iter().enumerate() {
+        match day_of(l) {
+            Some(d) if d == new_day => return None,
+            Some(d) if d > new_day => {
+                insert_at = Some(i);
+                break;
+            }
+            Some(_) => insert_at = Some(i + 1),
+            None => {}
+        }
+    }
+
+    let mut lines = lines;
+    lines.insert(insert_at.expect("the source lists at least one day"), line);
+    Some(lines.join("\n") + "\n")
+}
+
+fn read(path: &str) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|e| Error::io(path, e))
+}
+
+fn write(path: impl Into<PathBuf>, content: &str) -> Result<()> {
+    let path = path.into();
+    std::fs::write(&path, content).map_err(|e| Error::io(path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_source() {
+        let source = module_source(13);
+        assert!(source.contains("pub struct Day13;"));
+        assert!(source.contains("impl Solution for Day13 {"));
+        assert!(!source.contains("NN"));
+    }
+
+    #[test]
+    fn test_fuzz_target() {
+        let source = fuzz_target_source(13);
+        assert!(source.contains("use aoc25::day13::Day13;"));
+        assert!(source.contains("Day13::parse(input)"));
+
+        let manifest = add_fuzz_target("[[bin]]\nname = \"day12\"\n", 13);
+        assert!(manifest.ends_with(
+            "name = \"day12\"\n\n[[bin]]\nname = \"day13\"\npath = \"fuzz_targets/day13.rs\"\ntest = false\ndoc = false\nbench = false\n"
+        ));
+    }
+
+    #[test]
+    fn test_add_module() {
+        let lib =
+            "pub mod bench;\npub mod day01;\npub mod day12;\npub mod day6a;\npub mod error;\n";
+        assert_eq!(
+            add_module(lib, 13).unwrap(),
+            "pub mod bench;\npub mod day01;\npub mod day12;\npub mod day13;\npub mod day6a;\npub mod error;\n"
+        );
+        assert_eq!(
+            add_module(lib, 2).unwrap(),
+            "pub mod bench;\npub mod day01;\npub mod day02;\npub mod day12;\npub mod day6a;\npub mod error;\n"
+        );
+        assert!(add_module(lib, 12).is_err());
+    }
+
+    #[test]
+    fn test_register_day() {
+        let registry = "\
+pub const DAYS: &[Day] = &[
+    Day::new::<crate::day01::Day01>(1),
+    Day::new::<crate::day12::Day12>(12),
+];
+";
+        let registered = register_day(registry, 13).unwrap();
+        assert!(registered.contains(
+            "    Day::new::<crate::day12::Day12>(12),\n    Day::new::<crate::day13::Day13>(13),\n];"
+        ));
+        assert!(register_day(registry, 1).is_err());
+    }
+}
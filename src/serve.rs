@@ -0,0 +1,294 @@
+// A small JSON API over the solvers for tools on the same machine that would rather not shell out
+// to the binary. The server only binds localhost and answers requests on a fixed pool of workers,
+// through the same `runner::run` the CLI uses, so a slow day doesn't hold up the others. Bodies
+// larger than any puzzle input are turned away, and a solver that runs longer than the timeout is
+// answered with an error (it keeps its thread busy until the process exits, but not a worker).
+//
+//   POST /days/{day}/parts/{part}  solve one part of the input in the request body
+//   POST /days/{day}               solve both parts, answered with one report per part
+
+use std::io::Read;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::json;
+use tiny_http::{Header, Method, Request};
+
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::registry;
+use crate::runner::{self, DayRun, Report, Status};
+use crate::solution::Part;
+
+pub const DEFAULT_PORT: u16 = 2025;
+
+/// Requests answered at the same time, the others wait for a worker.
+pub const WORKERS: usize = 4;
+
+/// The largest body accepted, the real inputs are a few tens of KiB.
+pub const MAX_BODY: usize = 1 << 20;
+
+/// What the reports say the input was, the request body has no path.
+const INPUT: &str = "<request body>";
+
+pub struct Server {
+    server: tiny_http::Server,
+    addr: SocketAddr,
+    timeout: Duration,
+}
+
+/// A status code and the JSON body to send with it.
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: serde_json::Value,
+}
+
+impl Response {
+    fn json(status: u16, body: &impl Serialize) -> Response {
+        Response {
+            status,
+            body: serde_json::to_value(body).expect("reports serialize to json"),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Response {
+        Response {
+            status,
+            body: json!({ "status": Status::Error, "error": message.into() }),
+        }
+    }
+}
+
+/// Serves the API on the port until the process is stopped, giving up on a solver after
+/// `timeout`.
+pub fn serve(port: u16, timeout: Duration) -> Result<()> {
+    let server = bind(port, timeout)?;
+    println!("listening on http://{}", server.addr());
+    server.run();
+    Ok(())
+}
+
+/// Binds the port on localhost, 0 picks a free one.
+pub fn bind(port: u16, timeout: Duration) -> Result<Server> {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let listener = TcpListener::bind(addr).map_err(|e| Error::io(addr.to_string(), e))?;
+    let addr = listener
+        .local_addr()
+        .map_err(|e| Error::io(addr.to_string(), e))?;
+    let server = tiny_http::Server::from_listener(listener, None)
+        .map_err(|e| Error::io(addr.to_string(), std::io::Error::other(e)))?;
+    Ok(Server {
+        server,
+        addr,
+        timeout,
+    })
+}
+
+impl Server {
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Answers requests on `WORKERS` threads until the process is stopped.
+    pub fn run(self) {
+        let server = Arc::new(self.server);
+        let workers: Vec<_> = (0..WORKERS)
+            .map(|_| {
+                let server = Arc::clone(&server);
+                let timeout = self.timeout;
+                std::thread::spawn(move || {
+                    for request in server.incoming_requests() {
+                        respond(request, timeout);
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn respond(mut request: Request, timeout: Duration) {
+    let too_large = || Response::error(413, format!("the body is over {} bytes", MAX_BODY));
+    let response = if request.body_length().is_some_and(|n| n > MAX_BODY) {
+        too_large()
+    } else {
+        // a body without a length is read up to one byte past the limit to tell it went over
+        let mut body = String::new();
+        let limit = MAX_BODY as u64 + 1;
+        match request.as_reader().take(limit).read_to_string(&mut body) {
+            Ok(n) if n > MAX_BODY => too_large(),
+            Ok(_) => handle(request.method(), request.url(), &body, timeout),
+            Err(e) => Response::error(400, format!("the body is not valid utf-8 text: {}", e)),
+        }
+    };
+    tracing::info!(
+        method = %request.method(),
+        url = request.url(),
+        status = response.status,
+        "answered"
+    );
+
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("a valid header");
+    let reply = tiny_http::Response::from_string(response.body.to_string())
+        .with_status_code(response.status)
+        .with_header(content_type);
+    if let Err(e) = request.respond(reply) {
+        tracing::warn!(error = %e, "could not send the response");
+    }
+}
+
+/// Routes a request and solves the input in its body. A day that fails to parse or solve the
+/// input is answered with 422 and the error in the report, one that takes longer than `timeout`
+/// with 503.
+pub fn handle(method: &Method, url: &str, body: &str, timeout: Duration) -> Response {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    let (day, parts) = match segments[..] {
+        ["days", day] => (day, Part::BOTH.to_vec()),
+        ["days", day, "parts", part] => match part {
+            "1" => (day, vec![Part::One]),
+            "2" => (day, vec![Part::Two]),
+            _ => return Response::error(404, format!("there is no part {}", part)),
+        },
+        _ => return Response::error(404, format!("no such endpoint {}", path)),
+    };
+    let Some(day) = day.parse().ok().and_then(registry::find) else {
+        return Response::error(404, format!("day {} is not implemented", day));
+    };
+    if *method != Method::Post {
+        return Response::error(405, "post the puzzle input to this endpoint");
+    }
+
+    // a solver that panics on odd input takes down this request, not the server
+    let input = Input::new(INPUT, body, day.normalize());
+    let solving = parts.clone();
+    let run = runner::with_timeout(&format!("day {}", day.day), timeout, move || {
+        Ok(panic::catch_unwind(AssertUnwindSafe(|| {
+            runner::run_input(day, &input, &solving, None)
+        })))
+    });
+    let run = match run {
+        Ok(Ok(run)) => run,
+        Ok(Err(_)) => return Response::error(500, format!("the day {} solver panicked", day.day)),
+        Err(e) => return Response::error(503, e.to_string()),
+    };
+
+    let reports = Report::from_run(day.day, INPUT, &parts, &run);
+    let status = if run.as_ref().is_ok_and(DayRun::solved) {
+        200
+    } else {
+        422
+    };
+    match &reports[..] {
+        [report] => Response::json(status, report),
+        _ => Response::json(status, &reports),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    const TIMEOUT: Duration = Duration::from_secs(60);
+
+    fn example(day: u8) -> &'static str {
+        registry::find(day).unwrap().examples()[0].input
+    }
+
+    #[test]
+    fn test_solve_one_part() {
+        let response = handle(&Method::Post, "/days/1/parts/2", example(1), TIMEOUT);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["day"], 1);
+        assert_eq!(response.body["part"], 2);
+        assert_eq!(response.body["status"], "ok");
+        assert_eq!(response.body["answer"], 6);
+        assert!(response.body["solve_ns"].is_u64());
+
+        let both = handle(&Method::Post, "/days/4/", example(4), TIMEOUT);
+        assert_eq!(both.status, 200);
+        assert_eq!(both.body[0]["answer"], 13);
+        assert_eq!(both.body[1]["answer"], 43);
+    }
+
+    #[test]
+    fn test_errors() {
+        let response = handle(&Method::Post, "/days/4/parts/1", "..@\n.?.", TIMEOUT);
+        assert_eq!(response.status, 422);
+        assert_eq!(
+            response.body["error"],
+            "<request body>: parse error at line 2, column 2: unexpected character '?'"
+        );
+
+        let status = |method, url| handle(&method, url, "", TIMEOUT).status;
+        assert_eq!(status(Method::Post, "/days/25/parts/1"), 404);
+        assert_eq!(status(Method::Post, "/days/1/parts/3"), 404);
+        assert_eq!(status(Method::Post, "/day/1"), 404);
+        assert_eq!(status(Method::Get, "/days/1/parts/1"), 405);
+    }
+
+    #[test]
+    fn test_serve_over_http() {
+        let server = bind(0, TIMEOUT).unwrap();
+        let addr = server.addr();
+        std::thread::spawn(move || server.run());
+
+        let body = example(1);
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST /days/1/parts/1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply).unwrap();
+
+        assert!(reply.starts_with("HTTP/1.1 200"), "{}", reply);
+        assert!(reply.contains("application/json"), "{}", reply);
+        assert!(reply.contains(r#""answer":3"#), "{}", reply);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let response = handle(&Method::Post, "/days/10", example(10), Duration::ZERO);
+        assert_eq!(response.status, 503);
+        assert_eq!(
+            response.body["error"],
+            "timed out: gave up on day 10 after 0ns"
+        );
+    }
+
+    #[test]
+    fn test_body_too_large() {
+        let server = bind(0, TIMEOUT).unwrap();
+        let addr = server.addr();
+        std::thread::spawn(move || server.run());
+
+        // the length alone turns the request away, the body is never read
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST /days/1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n",
+            MAX_BODY + 1
+        )
+        .unwrap();
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply).unwrap();
+
+        assert!(reply.starts_with("HTTP/1.1 413"), "{}", reply);
+    }
+}
@@ -0,0 +1,327 @@
+use std::any::Any;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::input::Normalize;
+
+/// A puzzle answer. Most days produce a number, but some puzzles ask for text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Int(i64),
+    /// A number too large for an `Int`. Numbers that fit are always an `Int`, so equal answers
+    /// compare equal whatever type the solver returned.
+    #[serde(serialize_with = "serialize_big")]
+    Big(u128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+// json numbers above u64::MAX aren't read back as numbers by most tools, they are written as strings
+fn serialize_big<S: serde::Serializer>(n: &u128, s: S) -> std::result::Result<S::Ok, S::Error> {
+    match u64::try_from(*n) {
+        Ok(n) => s.serialize_u64(n),
+        Err(_) => s.serialize_str(&n.to_string()),
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::Int(n),
+                        // only unsigned types get here, the signed ones all fit
+                        Err(_) => Answer::Big(n as u128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, u32, i64, u64, usize, u128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// One half of a day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Serialize for Part {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_u8(match self {
+            Part::One => 1,
+            Part::Two => 2,
+        })
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+/// A worked example from the puzzle text. The expected answers are `None` when the puzzle doesn't
+/// state one for this input, or when the solver is tuned to the real input (day 8 part 1).
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+}
+
+/// One named way of solving a part, for days that kept more than one approach around.
+pub struct Strategy<I, A> {
+    pub name: &'static str,
+    pub solve: fn(&I) -> Result<A>,
+}
+
+/// The name of the only strategy of a part that doesn't register any.
+pub const DEFAULT_STRATEGY: &str = "default";
+
+/// A single day's puzzle: one parse step shared by both parts, then two independent solvers.
+///
+/// Solvers must not print their answers, they return them so the caller decides what to do with
+/// them. Malformed input is reported through the crate's `Error` rather than by panicking.
+pub trait Solution {
+    /// The parsed puzzle input handed to both parts.
+    type Input: 'static;
+    type Answer1: Into<Answer> + 'static;
+    type Answer2: Into<Answer> + 'static;
+
+    /// The examples given in the puzzle text, used by `--example` and the tests.
+    const EXAMPLES: &'static [Example] = &[];
+
+    /// Every approach to part 1 by name, picked with `--strategy` and timed against each other by
+    /// `compare`. The first is the one `part1` uses.
+    const PART1_STRATEGIES: &'static [Strategy<Self::Input, Self::Answer1>] = &[];
+
+    /// The same for part 2.
+    const PART2_STRATEGIES: &'static [Strategy<Self::Input, Self::Answer2>] = &[];
+
+    /// How inputs read from a file are cleaned up before `parse` sees them.
+    const NORMALIZE: Normalize = Normalize::ALL;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+}
+
+/// A registry entry for a day. The `Solution` types are erased so that days with different inputs
+/// and answers can live side by side in the registry.
+pub struct Day {
+    pub day: u8,
+    examples: &'static [Example],
+    normalize: Normalize,
+    parse: fn(&str) -> Result<Box<dyn Any>>,
+    part1: fn(&dyn Any) -> Result<Answer>,
+    part2: fn(&dyn Any) -> Result<Answer>,
+    strategies: fn(Part) -> Vec<&'static str>,
+    solve_with: fn(&dyn Any, Part, &str) -> Option<Result<Answer>>,
+}
+
+impl Day {
+    pub const fn new<S>(day: u8) -> Day
+    where
+        S: Solution,
+        S::Input: 'static,
+    {
+        Day {
+            day,
+            examples: S::EXAMPLES,
+            normalize: S::NORMALIZE,
+            parse: parse_erased::<S>,
+            part1: part1_erased::<S>,
+            part2: part2_erased::<S>,
+            strategies: strategies_erased::<S>,
+            solve_with: solve_with_erased::<S>,
+        }
+    }
+
+    /// The default puzzle input for the day, e.g. `input/day07.txt`.
+    pub fn input_path(&self) -> String {
+        format!("{}/day{:02}.txt", crate::input::DEFAULT_DIR, self.day)
+    }
+
+    pub fn normalize(&self) -> Normalize {
+        self.normalize
+    }
+
+    pub fn examples(&self) -> &'static [Example] {
+        self.examples
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        (self.parse)(input)
+    }
+
+    /// Runs one part against input produced by this day's `parse`.
+    pub fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => (self.part1)(input),
+            Part::Two => (self.part2)(input),
+        }
+    }
+
+    /// The names of the strategies for a part, the one `solve` uses first.
+    pub fn strategies(&self, part: Part) -> Vec<&'static str> {
+        let names = (self.strategies)(part);
+        if names.is_empty() {
+            vec![DEFAULT_STRATEGY]
+        } else {
+            names
+        }
+    }
+
+    /// Runs one part with the named strategy, `None` if the part has no strategy by that name.
+    pub fn solve_with(
+        &self,
+        input: &dyn Any,
+        part: Part,
+        strategy: &str,
+    ) -> Option<Result<Answer>> {
+        if (self.strategies)(part).is_empty() && strategy == DEFAULT_STRATEGY {
+            return Some(self.solve(input, part));
+        }
+        (self.solve_with)(input, part, strategy)
+    }
+}
+
+fn parse_erased<S>(input: &str) -> Result<Box<dyn Any>>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    S::parse(input).map(|parsed| Box::new(parsed) as Box<dyn Any>)
+}
+
+fn part1_erased<S>(input: &dyn Any) -> Result<Answer>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    S::part1(downcast_input::<S>(input)).map(Into::into)
+}
+
+fn part2_erased<S>(input: &dyn Any) -> Result<Answer>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    S::part2(downcast_input::<S>(input)).map(Into::into)
+}
+
+fn strategies_erased<S>(part: Part) -> Vec<&'static str>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    match part {
+        Part::One => S::PART1_STRATEGIES.iter().map(|s| s.name).collect(),
+        Part::Two => S::PART2_STRATEGIES.iter().map(|s| s.name).collect(),
+    }
+}
+
+fn solve_with_erased<S>(input: &dyn Any, part: Part, strategy: &str) -> Option<Result<Answer>>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    let input = downcast_input::<S>(input);
+    match part {
+        Part::One => S::PART1_STRATEGIES
+            .iter()
+            .find(|s| s.name == strategy)
+            .map(|s| (s.solve)(input).map(Into::into)),
+        Part::Two => S::PART2_STRATEGIES
+            .iter()
+            .find(|s| s.name == strategy)
+            .map(|s| (s.solve)(input).map(Into::into)),
+    }
+}
+
+fn downcast_input<S>(input: &dyn Any) -> &S::Input
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    input
+        .downcast_ref::<S::Input>()
+        .expect("input was not produced by this day's parser")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_conversions() {
+        assert_eq!(Answer::from(42u32), Answer::Int(42));
+        assert_eq!(Answer::from(-7i32), Answer::Int(-7));
+        assert_eq!(
+            Answer::from(390684413472684u64),
+            Answer::Int(390684413472684)
+        );
+        assert_eq!(Answer::from(7u128), Answer::Int(7));
+        assert_eq!(Answer::from(u64::MAX), Answer::Big(u64::MAX as u128));
+        assert_eq!(Answer::from(u64::MAX as usize), Answer::from(u64::MAX));
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from("abc"), Answer::Text("abc".to_string()));
+        assert_eq!(Answer::Int(5872).to_string(), "5872");
+    }
+
+    #[test]
+    fn test_answer_json() {
+        let json = |answer: Answer| serde_json::to_string(&answer).unwrap();
+        assert_eq!(json(Answer::Int(-3)), "-3");
+        assert_eq!(json(Answer::from(u64::MAX)), u64::MAX.to_string());
+        assert_eq!(json(Answer::from(u128::MAX)), format!("\"{}\"", u128::MAX));
+        assert_eq!(json(Answer::from("abc")), "\"abc\"");
+    }
+}
@@ -0,0 +1,615 @@
+// Step by step terminal animations of the grid based days, built from the solvers' own state: the
+// waves of rolls removed on day 4, the beams moving down on day 7, the ever larger rectangles found
+// inside the polygon on day 9 and the pieces placed and taken back on day 12. All frames are built
+// up front so the player can step backwards as well as forwards.
+
+use std::collections::HashSet;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::{Color, Stylize};
+use crossterm::{cursor, execute, queue, terminal};
+use itertools::Itertools;
+
+use crate::day04::{Day04, Floor, Tile};
+use crate::day07::{Day07, Grid};
+use crate::day09::{Day09, FloorPlan};
+use crate::day12::{self, Day12, ProblemData, Step};
+use crate::error::{Error, Result};
+use crate::export;
+use crate::geometry::{Point, Rect};
+use crate::input::Input;
+use crate::solution::Solution;
+
+/// Days with a visualization.
+pub const DAYS: &[u8] = &[4, 7, 9, 12];
+
+/// The search on day 12 can backtrack for a very long time, the trace stops after this many steps.
+pub const MAX_STEPS: usize = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Plain,
+    Dim,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Cyan,
+    /// The n-th piece placed, pieces cycle through a palette.
+    Piece(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Colour,
+}
+
+impl Cell {
+    pub const fn new(glyph: char, colour: Colour) -> Cell {
+        Cell { glyph, colour }
+    }
+}
+
+/// One step of an animation: a caption saying what happened and the grid after it.
+pub struct Frame {
+    pub caption: String,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+pub struct Options {
+    /// The region to pack on day 12, counting from 0.
+    pub region: usize,
+    /// The largest picture, in cells, for a day whose coordinates have to be scaled down (day 9).
+    pub size: (usize, usize),
+}
+
+/// Plays the day's animation in the terminal, sized to fit it, or writes a picture of it to
+/// `export` instead.
+pub fn visualize(
+    day: u8,
+    input: &Input,
+    region: usize,
+    delay: Duration,
+    export: Option<&Path>,
+) -> Result<()> {
+    if let Some(path) = export {
+        return export::export(day, &input.text, region, path).map_err(|e| input.context(e));
+    }
+    // leave a line for the caption and one for the key help
+    let (width, height) = terminal::size().unwrap_or((100, 40));
+    let options = Options {
+        region,
+        size: (width as usize, height.saturating_sub(2) as usize),
+    };
+    let frames = frames(day, &input.text, &options).map_err(|e| input.context(e))?;
+    play(&frames, delay)
+}
+
+pub fn frames(day: u8, input: &str, options: &Options) -> Result<Vec<Frame>> {
+    match day {
+        4 => Ok(removal_frames(&Day04::parse(input)?)),
+        7 => beam_frames(Day07::parse(input)?),
+        9 => Ok(rectangle_frames(&Day09::parse(input)?, options.size)),
+        12 => packing_frames(&Day12::parse(input)?, options.region),
+        _ => {
+            let days: Vec<String> = DAYS.iter().map(|d| d.to_string()).collect();
+            Err(Error::InvalidInput(format!(
+                "there is no visualization for day {}, only for days {}",
+                day,
+                days.join(", ")
+            )))
+        }
+    }
+}
+
+// day 4: every wave of accessible rolls is highlighted, then removed
+
+fn removal_frames(floor: &Floor) -> Vec<Frame> {
+    let mut rolls: Vec<Vec<bool>> = floor
+        .tiles()
+        .rows()
+        .map(|row| row.iter().map(|tile| *tile != Tile::EmptySpace).collect())
+        .collect();
+    let render = |rolls: &[Vec<bool>], wave: &HashSet<(usize, usize)>| {
+        rolls
+            .iter()
+            .enumerate()
+            .map(|(r, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(c, &roll)| match roll {
+                        true if wave.contains(&(r, c)) => Cell::new('x', Colour::Red),
+                        true => Cell::new('@', Colour::Green),
+                        false => Cell::new('.', Colour::Dim),
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+
+    let total: usize = rolls.iter().flatten().filter(|&&roll| roll).count();
+    let mut frames = vec![Frame {
+        caption: format!("{} rolls of paper", total),
+        rows: render(&rolls, &HashSet::new()),
+    }];
+
+    let mut removed = 0;
+    for (i, wave) in floor.removal_waves().iter().enumerate() {
+        let wave: HashSet<(usize, usize)> = wave.iter().copied().collect();
+        frames.push(Frame {
+            caption: format!("wave {}: {} rolls can be reached", i + 1, wave.len()),
+            rows: render(&rolls, &wave),
+        });
+
+        for &(r, c) in &wave {
+            rolls[r][c] = false;
+        }
+        removed += wave.len();
+        frames.push(Frame {
+            caption: format!("wave {}: {} of {} rolls removed", i + 1, removed, total),
+            rows: render(&rolls, &HashSet::new()),
+        });
+    }
+    frames
+}
+
+// day 7: the beams move down one row per frame
+
+fn beam_frames(mut grid: Grid) -> Result<Vec<Frame>> {
+    let render = |grid: &Grid| {
+        (0..grid.height())
+            .map(|r| {
+                (0..grid.width())
+                    .map(|c| match grid.glyph(r, c) {
+                        '^' if r > 0 && grid.has_beam(r - 1, c) => Cell::new('^', Colour::Red),
+                        '.' if grid.has_beam(r, c) => Cell::new('|', Colour::Cyan),
+                        '.' => Cell::new('.', Colour::Dim),
+                        'S' => Cell::new('S', Colour::Yellow),
+                        glyph => Cell::new(glyph, Colour::Plain),
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+
+    let mut frames = vec![Frame {
+        caption: "the beam leaves the source".to_string(),
+        rows: render(&grid),
+    }];
+    for row in 1..grid.height() {
+        grid.trace_row(row)?;
+        frames.push(Frame {
+            caption: format!(
+                "row {} of {}: the beam has been split {} times",
+                row + 1,
+                grid.height(),
+                grid.split_count()
+            ),
+            rows: render(&grid),
+        });
+    }
+    Ok(frames)
+}
+
+// day 9: the polygon scaled down to fit, then every larger rectangle the search finds inside it
+
+fn rectangle_frames(plan: &FloorPlan, (width, height): (usize, usize)) -> Vec<Frame> {
+    let points = plan.points();
+    let Some(bounds) = Rect::bounding(points) else {
+        return Vec::new();
+    };
+    let (min_x, max_x) = (bounds.min.x, bounds.max.x);
+    let (min_y, max_y) = (bounds.min.y, bounds.max.y);
+
+    // every cell stands for a block of tiles, and is drawn as the tile in the middle of the block
+    let scale_x = ((max_x - min_x) as usize / width.max(1) + 1) as i32;
+    let scale_y = ((max_y - min_y) as usize / height.max(1) + 1) as i32;
+    let columns = ((max_x - min_x) / scale_x + 1) as usize;
+    let rows = ((max_y - min_y) / scale_y + 1) as usize;
+    let tile = |row: usize, col: usize| {
+        Point::new(
+            (min_x + col as i32 * scale_x + scale_x / 2).min(max_x),
+            (min_y + row as i32 * scale_y + scale_y / 2).min(max_y),
+        )
+    };
+    let cell = |p: &Point| {
+        (
+            ((p.y - min_y) / scale_y) as usize,
+            ((p.x - min_x) / scale_x) as usize,
+        )
+    };
+
+    let red: HashSet<(usize, usize)> = points.iter().map(cell).collect();
+    let polygon: Vec<Vec<Cell>> = (0..rows)
+        .map(|r| {
+            (0..columns)
+                .map(|c| {
+                    if red.contains(&(r, c)) {
+                        Cell::new('#', Colour::Red)
+                    } else if plan.contains(&tile(r, c)) {
+                        Cell::new('.', Colour::Green)
+                    } else {
+                        Cell::new(' ', Colour::Plain)
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut frames = vec![Frame {
+        caption: format!(
+            "{} red tiles, one cell is {}x{} tiles",
+            points.len(),
+            scale_x,
+            scale_y
+        ),
+        rows: polygon.clone(),
+    }];
+
+    let improvements = plan.valid_rectangle_improvements();
+    for (i, rect) in improvements.iter().enumerate() {
+        let (top, left) = cell(&rect.min);
+        let (bottom, right) = cell(&rect.max);
+        let mut rows = polygon.clone();
+        for row in &mut rows[top..=bottom] {
+            for cell in &mut row[left..=right] {
+                if cell.colour != Colour::Red {
+                    *cell = Cell::new('o', Colour::Blue);
+                }
+            }
+        }
+
+        let last = if i + 1 == improvements.len() {
+            ", the largest"
+        } else {
+            ""
+        };
+        frames.push(Frame {
+            caption: format!(
+                "rectangle {}: {},{} to {},{} covers {} tiles{}",
+                i + 1,
+                rect.min.x,
+                rect.min.y,
+                rect.max.x,
+                rect.max.y,
+                rect.area(),
+                last
+            ),
+            rows,
+        });
+    }
+    frames
+}
+
+// day 12: every piece placed or taken back by the search in one region
+
+fn packing_frames(data: &ProblemData, region: usize) -> Result<Vec<Frame>> {
+    let region_index = region;
+    let region = data.regions.get(region).ok_or_else(|| {
+        Error::InvalidInput(format!(
+            "there is no region {}, the input has {} regions",
+            region,
+            data.regions.len()
+        ))
+    })?;
+    let (steps, fits) = day12::trace_region(region, &data.shapes, MAX_STEPS);
+
+    // the piece covering each cell, as (the order it was placed in, its shape)
+    let mut covered: Vec<Vec<Option<(usize, usize)>>> =
+        vec![vec![None; region.width]; region.height];
+    let render = |covered: &[Vec<Option<(usize, usize)>>]| {
+        covered
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        Some((piece, shape)) => Cell::new(
+                            char::from_digit(*shape as u32 % 36, 36).unwrap(),
+                            Colour::Piece(*piece),
+                        ),
+                        None => Cell::new('.', Colour::Dim),
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+
+    let pieces: usize = region.required.iter().sum();
+    let mut frames = vec![Frame {
+        caption: format!(
+            "region {}: {} pieces to place in {}x{}",
+            region_index, pieces, region.width, region.height
+        ),
+        rows: render(&covered),
+    }];
+
+    let (mut placed, mut backtracked) = (0, 0);
+    for step in &steps {
+        let caption = match step {
+            Step::Place { shape, cells } => {
+                for p in cells {
+                    covered[p.y as usize][p.x as usize] = Some((placed, *shape));
+                }
+                placed += 1;
+                format!("placed a piece of shape {}", shape)
+            }
+            Step::Remove { cells } => {
+                for p in cells {
+                    covered[p.y as usize][p.x as usize] = None;
+                }
+                backtracked += 1;
+                "took the last piece back".to_string()
+            }
+        };
+        frames.push(Frame {
+            caption: format!("{}, backtracked {} times", caption, backtracked),
+            rows: render(&covered),
+        });
+    }
+
+    let outcome = match fits {
+        Some(true) => "the pieces fit".to_string(),
+        Some(false) => "the pieces don't fit".to_string(),
+        None => format!("gave up after {} steps", MAX_STEPS),
+    };
+    let last = frames.last_mut().expect("there is a first frame");
+    last.caption = format!("{}: {}", last.caption, outcome);
+    Ok(frames)
+}
+
+// ============ Player ============
+
+const HELP: &str = "space play/pause  \u{2190}/\u{2192} step  home/end first/last  \
+                    w/a/s/d scroll  +/- speed  q quit";
+
+/// Plays the frames in the terminal. When stdout isn't a terminal the frames are printed one
+/// after another, without colours.
+pub fn play(frames: &[Frame], delay: Duration) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        for frame in frames {
+            writeln!(stdout, "{}", frame.caption).map_err(terminal_error)?;
+            for row in &frame.rows {
+                let line: String = row.iter().map(|cell| cell.glyph).collect();
+                writeln!(stdout, "{}", line).map_err(terminal_error)?;
+            }
+            writeln!(stdout).map_err(terminal_error)?;
+        }
+        return Ok(());
+    }
+    if frames.is_empty() {
+        return Ok(());
+    }
+
+    terminal::enable_raw_mode().map_err(terminal_error)?;
+    let _restore = RestoreTerminal;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide).map_err(terminal_error)?;
+
+    let last = frames.len() - 1;
+    let (mut index, mut playing, mut delay) = (0, false, delay);
+    let (mut top, mut left) = (0, 0);
+    loop {
+        draw(&mut stdout, frames, index, playing, (top, left)).map_err(terminal_error)?;
+
+        // while playing, a key press just interrupts the wait for the next frame
+        let timeout = if playing {
+            delay
+        } else {
+            Duration::from_secs(60)
+        };
+        if !event::poll(timeout).map_err(terminal_error)? {
+            if playing {
+                index = (index + 1).min(last);
+                playing = index < last;
+            }
+            continue;
+        }
+
+        let Event::Key(key) = event::read().map_err(terminal_error)? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            KeyCode::Char(' ') => {
+                // playing from the last frame starts over
+                if index == last {
+                    index = 0;
+                }
+                playing = !playing;
+            }
+            KeyCode::Right => {
+                playing = false;
+                index = (index + 1).min(last);
+            }
+            KeyCode::Left => {
+                playing = false;
+                index = index.saturating_sub(1);
+            }
+            KeyCode::Home => index = 0,
+            KeyCode::End => index = last,
+            KeyCode::Char('w') | KeyCode::Up => top = top.saturating_sub(1),
+            KeyCode::Char('s') | KeyCode::Down => top += 1,
+            KeyCode::Char('a') => left = left.saturating_sub(1),
+            KeyCode::Char('d') => left += 1,
+            KeyCode::Char('+') => delay = (delay / 2).max(Duration::from_millis(5)),
+            KeyCode::Char('-') => delay = (delay * 2).min(Duration::from_secs(2)),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn draw(
+    out: &mut impl Write,
+    frames: &[Frame],
+    index: usize,
+    playing: bool,
+    (top, left): (usize, usize),
+) -> std::io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let (width, height) = (width as usize, height as usize);
+    let frame = &frames[index];
+
+    let state = if playing { "playing" } else { "paused" };
+    let caption = format!(
+        "{}/{} {}: {}",
+        index + 1,
+        frames.len(),
+        state,
+        frame.caption
+    );
+    queue!(
+        out,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(terminal::ClearType::All)
+    )?;
+    write!(out, "{}", fit(&caption, width).bold())?;
+
+    // the caption and the help take a line each
+    let visible = height.saturating_sub(2);
+    let top = top.min(frame.rows.len().saturating_sub(visible));
+    for (i, row) in frame.rows.iter().skip(top).take(visible).enumerate() {
+        queue!(out, cursor::MoveTo(0, i as u16 + 1))?;
+        let cells = row.iter().skip(left).take(width);
+        // one escape sequence per run of cells of the same colour
+        for (colour, run) in &cells.chunk_by(|cell| cell.colour) {
+            let text: String = run.map(|cell| cell.glyph).collect();
+            write!(out, "{}", text.with(colour.terminal_colour()))?;
+        }
+    }
+
+    queue!(out, cursor::MoveTo(0, height.saturating_sub(1) as u16))?;
+    write!(out, "{}", fit(HELP, width).dark_grey())?;
+    out.flush()
+}
+
+fn fit(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+impl Colour {
+    fn terminal_colour(self) -> Color {
+        const PIECES: [Color; 12] = [
+            Color::Red,
+            Color::Green,
+            Color::Yellow,
+            Color::Blue,
+            Color::Magenta,
+            Color::Cyan,
+            Color::DarkRed,
+            Color::DarkGreen,
+            Color::DarkYellow,
+            Color::DarkBlue,
+            Color::DarkMagenta,
+            Color::DarkCyan,
+        ];
+        match self {
+            Colour::Plain => Color::Reset,
+            Colour::Dim => Color::DarkGrey,
+            Colour::Red => Color::Red,
+            Colour::Green => Color::Green,
+            Colour::Yellow => Color::Yellow,
+            Colour::Blue => Color::Blue,
+            Colour::Cyan => Color::Cyan,
+            Colour::Piece(n) => PIECES[n % PIECES.len()],
+        }
+    }
+}
+
+// Leaves raw mode and the alternate screen however the player exits, errors included.
+struct RestoreTerminal;
+
+impl Drop for RestoreTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(
+            std::io::stdout(),
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn terminal_error(e: std::io::Error) -> Error {
+    Error::io("stdout", e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPTIONS: Options = Options {
+        region: 0,
+        size: (40, 20),
+    };
+
+    fn example(day: u8) -> &'static str {
+        crate::registry::find(day).unwrap().examples()[0].input
+    }
+
+    #[test]
+    fn test_removal_frames() {
+        let frames = frames(4, example(4), &OPTIONS).unwrap();
+        let waves = (frames.len() - 1) / 2;
+        assert_eq!(frames.len(), 2 * waves + 1);
+        assert_eq!(frames[1].caption, "wave 1: 13 rolls can be reached");
+        assert!(
+            frames
+                .last()
+                .unwrap()
+                .caption
+                .ends_with("43 of 71 rolls removed")
+        );
+    }
+
+    #[test]
+    fn test_beam_frames() {
+        let frames = frames(7, example(7), &OPTIONS).unwrap();
+        assert_eq!(frames.len(), 16);
+        assert_eq!(
+            frames.last().unwrap().caption,
+            "row 16 of 16: the beam has been split 21 times"
+        );
+        // the beam has reached the bottom row
+        assert!(frames[15].rows[15].iter().any(|cell| cell.glyph == '|'));
+    }
+
+    #[test]
+    fn test_rectangle_frames() {
+        let frames = frames(9, example(9), &OPTIONS).unwrap();
+        // the example fits without scaling, one cell per tile
+        assert_eq!(frames[0].rows.len(), 7);
+        assert_eq!(frames[0].rows[0].len(), 10);
+        assert!(
+            frames
+                .last()
+                .unwrap()
+                .caption
+                .ends_with("covers 24 tiles, the largest")
+        );
+    }
+
+    #[test]
+    fn test_packing_frames() {
+        let frames = frames(12, example(12), &OPTIONS).unwrap();
+        assert!(frames.last().unwrap().caption.ends_with("the pieces fit"));
+
+        let options = Options {
+            region: 3,
+            ..OPTIONS
+        };
+        assert!(super::frames(12, example(12), &options).is_err());
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        assert!(frames(1, example(1), &OPTIONS).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+// Re-runs a day whenever its input file changes, for iterating on a solver against an input being
+// edited. The file is polled rather than watched with inotify and friends so it works the same on
+// any machine, an input file is small enough that checking its size and modification time a few
+// times a second costs nothing.
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use crate::error::{Error, Result};
+use crate::input::Loader;
+use crate::runner::{self, DayRun};
+use crate::solution::{Answer, Day, Part};
+
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// What is compared between polls, an editor that saves a file by replacing it changes both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+fn stamp(path: &Path) -> Option<Stamp> {
+    let metadata = fs::metadata(path).ok()?;
+    Some(Stamp {
+        modified: metadata.modified().ok()?,
+        len: metadata.len(),
+    })
+}
+
+/// Runs the day against the file and again every time it changes, printing how the answers and
+/// timings moved since the last run that succeeded. Only returns if the file can't be read at the
+/// start, later errors are printed and the watch carries on.
+pub fn watch(day: &Day, loader: &Loader, path: &str, parts: &[Part]) -> Result<()> {
+    if path == "-" {
+        return Err(Error::InvalidInput(
+            "--watch needs an input file, stdin can't be watched".to_string(),
+        ));
+    }
+    let file = Path::new(path);
+    fs::metadata(file).map_err(|e| Error::io(file, e))?;
+
+    let mut seen = None;
+    let mut previous: Option<DayRun> = None;
+    loop {
+        let current = stamp(file);
+        // a file that is briefly missing while an editor saves it is picked up once it is back
+        if current.is_none() || current == seen {
+            std::thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+        seen = current;
+
+        if previous.is_some() {
+            println!();
+        }
+        println!("day {} on {}", day.day, path);
+        let run = loader
+            .load(day, Some(path))
+            .and_then(|input| runner::run_input(day, &input, parts, None));
+        match run {
+            Ok(run) => {
+                for line in compare(previous.as_ref(), &run) {
+                    println!("  {}", line);
+                }
+                previous = Some(run);
+            }
+            Err(e) => println!("  error: {}", e),
+        }
+        println!("watching for changes, ctrl-c to stop");
+    }
+}
+
+/// Describes a run, with what changed since `previous` when there is one.
+pub fn compare(previous: Option<&DayRun>, run: &DayRun) -> Vec<String> {
+    let mut lines = vec![format!(
+        "parse   {:>10}{}",
+        format!("{:.2?}", run.parse_time),
+        previous
+            .map(|p| timing_change(p.parse_time, run.parse_time))
+            .unwrap_or_default()
+    )];
+
+    for part in &run.parts {
+        let before = previous.and_then(|p| p.parts.iter().find(|b| b.part == part.part));
+        let now = describe(&part.answer);
+        let change = match before.map(|b| describe(&b.answer)) {
+            Some(before) if before == now => " (unchanged)".to_string(),
+            Some(before) => format!(" (was {})", before),
+            None => String::new(),
+        };
+        lines.push(format!(
+            "part {}  {:>10}{}  answer {}{}",
+            part.part,
+            format!("{:.2?}", part.solve_time),
+            before
+                .map(|b| timing_change(b.solve_time, part.solve_time))
+                .unwrap_or_default(),
+            now,
+            change
+        ));
+    }
+    lines
+}
+
+fn describe(answer: &Result<Answer>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+fn timing_change(before: Duration, after: Duration) -> String {
+    let percent = if before.is_zero() {
+        0.0
+    } else {
+        (after.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0
+    };
+    format!(
+        "  (was {:>10}, {:+.0}%)",
+        format!("{:.2?}", before),
+        percent
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::PartRun;
+
+    fn run(parse_ms: u64, answers: &[(Part, i64, u64)]) -> DayRun {
+        DayRun {
+            day: 9,
+            parse_time: Duration::from_millis(parse_ms),
+            parse_alloc: None,
+            parts: answers
+                .iter()
+                .map(|&(part, answer, solve_ms)| PartRun {
+                    part,
+                    answer: Ok(Answer::Int(answer)),
+                    solve_time: Duration::from_millis(solve_ms),
+                    solve_alloc: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let first = run(2, &[(Part::One, 50, 10), (Part::Two, 24, 40)]);
+        assert_eq!(
+            compare(None, &first),
+            [
+                "parse       2.00ms",
+                "part 1     10.00ms  answer 50",
+                "part 2     40.00ms  answer 24",
+            ]
+        );
+
+        let second = run(2, &[(Part::One, 50, 5), (Part::Two, 30, 50)]);
+        assert_eq!(
+            compare(Some(&first), &second),
+            [
+                "parse       2.00ms  (was     2.00ms, +0%)",
+                "part 1      5.00ms  (was    10.00ms, -50%)  answer 50 (unchanged)",
+                "part 2     50.00ms  (was    40.00ms, +25%)  answer 30 (was 24)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,36 @@
+// The allocator installed the way the binary installs it. A single test, so that no other test
+// allocates while it measures and the figures are exact.
+#![cfg(feature = "alloc-stats")]
+
+use aoc25::memory::{self, AllocStats, Counting};
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[test]
+fn test_counting_allocator() {
+    let (_, stats) = memory::measure(|| drop(std::hint::black_box(vec![0u8; 4096])));
+    assert_eq!(
+        stats,
+        Some(AllocStats {
+            bytes: 4096,
+            peak_bytes: 4096,
+            count: 1,
+        })
+    );
+
+    // growing a block counts what it grew by, shrinking it frees the tail
+    let mut block = Vec::<u8>::with_capacity(4096);
+    let (_, stats) = memory::measure(|| {
+        block.reserve_exact(8192);
+        block.shrink_to(1024);
+    });
+    assert_eq!(
+        stats,
+        Some(AllocStats {
+            bytes: 4096,
+            peak_bytes: 4096,
+            count: 2,
+        })
+    );
+}
@@ -0,0 +1,123 @@
+use aoc25::answers::{self, KnownAnswers, Verdict};
+use aoc25::day04::parse_floor;
+use aoc25::day07::Grid;
+use aoc25::day08::LightingSystem;
+use aoc25::day09::FloorPlan;
+use aoc25::day12::{can_fit_region, parse_input};
+use aoc25::solution::Part;
+use aoc25::{registry, runner};
+
+#[test]
+fn test_real_input_answers() {
+    let known = KnownAnswers::load(answers::DEFAULT_PATH).unwrap();
+
+    // only the days that are quick to solve in a debug build
+    for n in [1, 3, 5, 6, 9] {
+        let day = registry::find(n).unwrap();
+        let input = std::fs::read_to_string(day.input_path()).unwrap();
+        let run = runner::run(day, &input, &Part::BOTH).unwrap();
+
+        for part in run.parts {
+            let answer = part.answer.unwrap();
+            // answers.toml only holds the answers the puzzle site accepted so far, a day or part
+            // that isn't recorded yet is unknown rather than a failure
+            let verdict = known.check(n, part.part, &answer);
+            assert!(
+                !matches!(verdict, Verdict::Fail { .. }),
+                "day {} part {} answered {}, {:?}",
+                n,
+                part.part,
+                answer,
+                verdict
+            );
+        }
+    }
+}
+
+#[test]
+fn test_examples() {
+    for day in registry::DAYS {
+        for example in day.examples() {
+            for part in Part::BOTH {
+                let Some(expected) = example.expected(part) else {
+                    continue;
+                };
+                let run = runner::run(day, example.input, &[part]).unwrap();
+                assert_eq!(
+                    run.parts[0].answer.as_ref().unwrap(),
+                    expected,
+                    "day {} part {} example",
+                    day.day,
+                    part
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_strategies_on_examples() {
+    for day in registry::DAYS {
+        for example in day.examples() {
+            let parsed = day.parse(example.input).unwrap();
+            for part in Part::BOTH {
+                let Some(expected) = example.expected(part) else {
+                    continue;
+                };
+                for strategy in day.strategies(part) {
+                    let answer = day.solve_with(parsed.as_ref(), part, strategy).unwrap();
+                    assert_eq!(
+                        &answer.unwrap(),
+                        expected,
+                        "day {} part {} example with {}",
+                        day.day,
+                        part,
+                        strategy
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_day04_floor() {
+    let mut floor = parse_floor("..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.").unwrap();
+    assert_eq!(floor.process_accessible(), 13);
+    assert_eq!(floor.accessible_slots().len(), 13);
+}
+
+#[test]
+fn test_day07_grid() {
+    let mut grid = Grid::parse_grid(".S.\n...\n.^.\n...").unwrap();
+    assert_eq!(grid.count_all_paths(), 2);
+
+    grid.trace_tachyons_down().unwrap();
+    assert_eq!(grid.split_count(), 1);
+}
+
+#[test]
+fn test_day08_lighting_system() {
+    let mut system: LightingSystem = "0,0,0\n1,0,0\n10,0,0\n12,0,0".parse().unwrap();
+    assert_eq!(system.connect_junctions_n(2), 2);
+    assert_eq!(system.unique_circuits().count(), 2);
+
+    let mut system: LightingSystem = "0,0,0\n1,0,0\n10,0,0\n12,0,0".parse().unwrap();
+    let (a, b) = system.connect_into_single_circuit().unwrap();
+    assert_eq!((a.x, b.x), (1, 10));
+}
+
+#[test]
+fn test_day09_floor_plan() {
+    let plan: FloorPlan = "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3".parse().unwrap();
+    assert_eq!(plan.points().len(), 8);
+    assert_eq!(plan.find_largest_area(), 50);
+    assert_eq!(plan.find_largest_valid_rectangle(), 24);
+}
+
+#[test]
+fn test_day12_can_fit_region() {
+    let data = parse_input("0:\n###\n#..\n###\n\n4x4: 2\n3x3: 2").unwrap();
+    assert!(can_fit_region(&data.regions[0], &data.shapes));
+    assert!(!can_fit_region(&data.regions[1], &data.shapes));
+}
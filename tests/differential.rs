@@ -0,0 +1,13 @@
+use aoc25::differential::CHECKS;
+
+#[test]
+fn test_fast_solvers_agree_with_brute_force() {
+    for check in CHECKS {
+        if let Some(counterexample) = check.run(200, 0) {
+            panic!(
+                "{}: {:?} ({})",
+                check.name, counterexample, check.description
+            );
+        }
+    }
+}
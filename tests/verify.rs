@@ -0,0 +1,47 @@
+// --verify run through the binary, to check the exit status a commit hook would see.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+// Writes an answers file for the test to its own path in the temp directory.
+fn answers_file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("aoc25-{}-{}.toml", name, std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn verify(answers: &PathBuf, extra: &[&str]) -> bool {
+    let status = Command::new(env!("CARGO_BIN_EXE_aoc25"))
+        .args(["--day", "1", "--verify", "--answers"])
+        .arg(answers)
+        .args(extra)
+        .output()
+        .unwrap()
+        .status;
+    std::fs::remove_file(answers).unwrap();
+    status.success()
+}
+
+#[test]
+fn test_verify_exit_status() {
+    let input = std::fs::read_to_string("input/day01.txt").unwrap();
+    let day = aoc25::registry::find(1).unwrap();
+    let run = aoc25::runner::run(day, &input, &aoc25::solution::Part::BOTH).unwrap();
+    let (part1, part2) = (
+        run.parts[0].answer.as_ref().unwrap(),
+        run.parts[1].answer.as_ref().unwrap(),
+    );
+
+    let matching = format!("[day01]\npart1 = {}\npart2 = {}\n", part1, part2);
+    assert!(verify(&answers_file("matching", &matching), &[]));
+
+    let mismatch = format!("[day01]\npart1 = {}\npart2 = 1\n", part1);
+    assert!(!verify(&answers_file("mismatch", &mismatch), &[]));
+
+    // nothing known, nothing checked
+    assert!(!verify(&answers_file("empty", ""), &[]));
+
+    let partial = format!("[day01]\npart1 = {}\n", part1);
+    assert!(verify(&answers_file("partial", &partial), &[]));
+    assert!(!verify(&answers_file("strict", &partial), &["--strict"]));
+}